@@ -0,0 +1,80 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input,
+    Data, DeriveInput, Fields, LitInt,
+};
+
+
+/// Implement `EntryLimits` from `#[limit(...)]` field attributes
+///
+/// Supported limits are `max_len`, `min_len` (characters or items) and `max_bytes` (UTF-8 or raw
+/// bytes).  Fields are checked in declaration order and the first violation is reported.
+///
+/// The generated code refers to the `hdi_extensions` crate by name.
+#[proc_macro_derive(EntryLimits, attributes(limit))]
+pub fn derive_entry_limits(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!( input as DeriveInput );
+
+    match expand_entry_limits( input ) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn expand_entry_limits(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => Err(syn::Error::new_spanned(
+                &input.ident, "EntryLimits can only be derived for structs with named fields"
+            ))?,
+        },
+        _ => Err(syn::Error::new_spanned(
+            &input.ident, "EntryLimits can only be derived for structs"
+        ))?,
+    };
+
+    let mut checks = vec![];
+
+    for field in fields {
+        let ident = field.ident.as_ref().unwrap();
+        let name = ident.to_string();
+
+        for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("limit") ) {
+            attr.parse_nested_meta(|meta| {
+                let value : usize = meta.value()?.parse::<LitInt>()?.base10_parse()?;
+
+                let (variant, measure) = match meta.path.get_ident().map(|i| i.to_string() ).as_deref() {
+                    Some("max_len") => ( quote!( MaxLen ), quote!( LimitLength::limit_len ) ),
+                    Some("min_len") => ( quote!( MinLen ), quote!( LimitLength::limit_len ) ),
+                    Some("max_bytes") => ( quote!( MaxBytes ), quote!( LimitBytes::limit_bytes ) ),
+                    _ => Err(meta.error("Unknown limit; expected 'max_len', 'min_len' or 'max_bytes'"))?,
+                };
+
+                checks.push( quote! {
+                    if let Some(message) = ::hdi_extensions::FieldLimit::#variant( #value )
+                        .check( #name, ::hdi_extensions::#measure( &self.#ident ) )
+                    {
+                        return Some( message );
+                    }
+                });
+
+                Ok(())
+            })?;
+        }
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok( quote! {
+        impl #impl_generics ::hdi_extensions::EntryLimits for #ident #type_generics #where_clause {
+            fn limit_violation(&self) -> Option<String> {
+                #( #checks )*
+                None
+            }
+        }
+    })
+}
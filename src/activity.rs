@@ -0,0 +1,213 @@
+use core::convert::TryFrom;
+use hdi::prelude::{
+    must_get_agent_activity,
+    ExternResult, WasmError,
+    ActionHash, AgentPubKey,
+    Action, ActionType, Entry, EntryType,
+    ChainFilter, RegisterAgentActivity,
+    ScopedEntryDefIndex,
+};
+use crate::{
+    summon_entry,
+    ScopedTypeConnector,
+};
+
+
+/// Number of actions fetched per [`must_get_agent_activity`] call when walking a chain
+const ACTIVITY_BATCH_SIZE : u32 = 50;
+
+
+//
+// Agent Activity
+//
+/// Alias for [`must_get_agent_activity`] with results ordered from newest to oldest
+pub fn summon_agent_activity(
+    author: &AgentPubKey,
+    filter: ChainFilter,
+) -> ExternResult<Vec<RegisterAgentActivity>> {
+    let mut activity = must_get_agent_activity( author.to_owned(), filter )?;
+
+    activity.sort_by(|a, b| {
+        b.action.hashed.content.action_seq().cmp( &a.action.hashed.content.action_seq() )
+    });
+
+    Ok( activity )
+}
+
+/// Visit an author's actions starting at `from` and moving back toward genesis
+///
+/// The visitor returns `false` to stop.  Actions are fetched in batches so that only the needed
+/// part of the chain is requested.
+///
+/// ##### Example: Basic Usage
+/// ```
+/// # use hdi::prelude::*;
+/// # use hdi_extensions::*;
+///
+/// fn count_actions(author: AgentPubKey, prev_action: ActionHash) -> ExternResult<u32> {
+///     let mut count = 0;
+///     walk_chain_back( &author, &prev_action, |_| {
+///         count += 1;
+///         Ok( true )
+///     })?;
+///     Ok( count )
+/// }
+/// ```
+pub fn walk_chain_back<F>(
+    author: &AgentPubKey,
+    from: &ActionHash,
+    mut visitor: F,
+) -> ExternResult<()>
+where
+    F: FnMut(&RegisterAgentActivity) -> ExternResult<bool>,
+{
+    let mut next = Some( from.to_owned() );
+
+    while let Some(chain_top) = next {
+        let batch = summon_agent_activity(
+            author,
+            ChainFilter::new( chain_top ).take( ACTIVITY_BATCH_SIZE ),
+        )?;
+
+        for activity in batch.iter() {
+            if !visitor( activity )? {
+                return Ok(());
+            }
+        }
+
+        next = batch.last()
+            .and_then(|activity| activity.action.hashed.content.prev_action().cloned() );
+    }
+
+    Ok(())
+}
+
+/// Collect an author's actions back from `from` until the predicate matches (exclusive)
+pub fn take_chain_until<F>(
+    author: &AgentPubKey,
+    from: &ActionHash,
+    mut predicate: F,
+) -> ExternResult<Vec<RegisterAgentActivity>>
+where
+    F: FnMut(&RegisterAgentActivity) -> bool,
+{
+    let mut collected = vec![];
+
+    walk_chain_back( author, from, |activity| {
+        if predicate( activity ) {
+            return Ok( false );
+        }
+        collected.push( activity.to_owned() );
+        Ok( true )
+    })?;
+
+    Ok( collected )
+}
+
+/// Find the newest action back from `from` that matches the predicate
+pub fn find_in_chain<F>(
+    author: &AgentPubKey,
+    from: &ActionHash,
+    mut predicate: F,
+) -> ExternResult<Option<RegisterAgentActivity>>
+where
+    F: FnMut(&RegisterAgentActivity) -> bool,
+{
+    let mut found = None;
+
+    walk_chain_back( author, from, |activity| {
+        if predicate( activity ) {
+            found = Some( activity.to_owned() );
+            return Ok( false );
+        }
+        Ok( true )
+    })?;
+
+    Ok( found )
+}
+
+/// Find the author's newest action of the given [`ActionType`] back from `from`
+pub fn find_latest_action_of_type(
+    author: &AgentPubKey,
+    from: &ActionHash,
+    action_type: &ActionType,
+) -> ExternResult<Option<RegisterAgentActivity>> {
+    find_in_chain( author, from, |activity| {
+        activity.action.hashed.content.action_type() == *action_type
+    })
+}
+
+
+//
+// Typed Activity
+//
+/// Check if an activity item is a [`Create`](hdi::prelude::Create) of `T`'s entry type
+pub fn is_entry_create<T,ET,ETU>(activity: &RegisterAgentActivity) -> bool
+where
+    T: ScopedTypeConnector<ET,ETU>,
+    ScopedEntryDefIndex: for<'a> TryFrom<&'a ET, Error = WasmError>,
+{
+    match &activity.action.hashed.content {
+        Action::Create(create) => match &create.entry_type {
+            EntryType::App(app_entry_def) => *app_entry_def == T::app_entry_def(),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Count the author's creates of `T`'s entry type back from `from` to genesis
+pub fn count_entry_creates<T,ET,ETU>(
+    author: &AgentPubKey,
+    from: &ActionHash,
+) -> ExternResult<u32>
+where
+    T: ScopedTypeConnector<ET,ETU>,
+    ScopedEntryDefIndex: for<'a> TryFrom<&'a ET, Error = WasmError>,
+{
+    let mut count = 0;
+
+    walk_chain_back( author, from, |activity| {
+        if is_entry_create::<T,ET,ETU>( activity ) {
+            count += 1;
+        }
+        Ok( true )
+    })?;
+
+    Ok( count )
+}
+
+/// Find the author's newest create of `T`'s entry type back from `from`
+pub fn find_latest_entry_create<T,ET,ETU>(
+    author: &AgentPubKey,
+    from: &ActionHash,
+) -> ExternResult<Option<RegisterAgentActivity>>
+where
+    T: ScopedTypeConnector<ET,ETU>,
+    ScopedEntryDefIndex: for<'a> TryFrom<&'a ET, Error = WasmError>,
+{
+    find_in_chain( author, from, is_entry_create::<T,ET,ETU> )
+}
+
+/// Decode an activity item's entry into `T` if its app entry def matches
+///
+/// Uses the cached entry when present, otherwise the entry is summoned.
+pub fn decode_activity_entry<T,ET,ETU>(activity: &RegisterAgentActivity) -> ExternResult<Option<T>>
+where
+    T: ScopedTypeConnector<ET,ETU> + TryFrom<Entry, Error = WasmError>,
+    ScopedEntryDefIndex: for<'a> TryFrom<&'a ET, Error = WasmError>,
+{
+    let action = &activity.action.hashed.content;
+
+    match action.entry_data() {
+        Some((entry_hash, EntryType::App(app_entry_def))) if *app_entry_def == T::app_entry_def() => {
+            let entry = match &activity.cached_entry {
+                Some(entry) => entry.to_owned(),
+                None => summon_entry( entry_hash )?.content,
+            };
+
+            Ok( Some( T::try_from( entry )? ) )
+        },
+        _ => Ok( None ),
+    }
+}
@@ -0,0 +1,231 @@
+use core::convert::TryFrom;
+use hdi::prelude::{
+    hash_entry,
+    ExternResult, WasmError,
+    ActionHash, AgentPubKey, Signature,
+    Action, Create, Entry, EntryType,
+    CounterSigningSessionData, CounterSigningAgentState, PreflightRequest, Role,
+    ScopedEntryDefIndex,
+    ValidateCallbackResult,
+};
+use crate::{
+    guest_error,
+    summon_action,
+    summon_entry,
+    ScopedTypeConnector,
+};
+
+
+//
+// Session Data
+//
+/// Get the [`CounterSigningSessionData`] from a countersigned entry
+pub fn countersigning_session(entry: &Entry) -> ExternResult<CounterSigningSessionData> {
+    match entry {
+        Entry::CounterSign(session_data, _) => Ok( session_data.as_ref().to_owned() ),
+        _ => Err(guest_error!("Expected a countersigned entry")),
+    }
+}
+
+/// Get the [`CounterSigningSessionData`] for a countersigned [`Create`]
+pub fn summon_countersigning_session(create: &Create) -> ExternResult<CounterSigningSessionData> {
+    let entry = summon_entry( &create.entry_hash )?.content;
+
+    countersigning_session( &entry )
+        .map_err(|_| guest_error!(
+            "Create ({}) entry is not countersigned", create.entry_hash
+        ))
+}
+
+/// Get the [`PreflightRequest`] for a countersigned [`Create`]
+pub fn summon_preflight_request(create: &Create) -> ExternResult<PreflightRequest> {
+    Ok( summon_countersigning_session( create )?.preflight_request().to_owned() )
+}
+
+/// Get the roles assigned to an agent in a preflight request (`None` if not a participant)
+///
+/// Both required and optional signing agents are participants.
+pub fn participant_roles(preflight: &PreflightRequest, agent: &AgentPubKey) -> Option<Vec<Role>> {
+    preflight.signing_agents.iter()
+        .chain( preflight.optional_signing_agents.iter() )
+        .find(|(signing_agent, _)| signing_agent == agent )
+        .map(|(_, roles)| roles.to_owned() )
+}
+
+fn participant_state<'a>(
+    session: &'a CounterSigningSessionData,
+    agent: &AgentPubKey,
+) -> Option<&'a CounterSigningAgentState> {
+    let preflight = session.preflight_request();
+    let find_state = |agents: &[(AgentPubKey, Vec<Role>)], responses: &'a [(CounterSigningAgentState, Signature)]| {
+        agents.iter()
+            .position(|(signing_agent, _)| signing_agent == agent )
+            .and_then(|index| responses.iter()
+                .map(|(state, _)| state )
+                .find(|state| *state.agent_index() as usize == index )
+            )
+    };
+
+    find_state( &preflight.signing_agents, &session.responses )
+        .or_else(|| find_state( &preflight.optional_signing_agents, &session.optional_responses ) )
+}
+
+
+//
+// Validation
+//
+/// Require each listed agent to be a participant with at least the listed roles
+///
+/// ##### Example: Basic Usage
+/// ```
+/// # use hdi::prelude::*;
+/// # use hdi_extensions::*;
+///
+/// fn check(create: Create, buyer: AgentPubKey, seller: AgentPubKey) -> ExternResult<ValidateCallbackResult> {
+///     let preflight = summon_preflight_request( &create )?;
+///     require_participants( &preflight, &[ (buyer, vec![ Role(0) ]), (seller, vec![ Role(1) ]) ] )
+/// }
+/// ```
+pub fn require_participants(
+    preflight: &PreflightRequest,
+    required: &[(AgentPubKey, Vec<Role>)],
+) -> ExternResult<ValidateCallbackResult> {
+    for (agent, required_roles) in required {
+        let roles = match participant_roles( preflight, agent ) {
+            Some(roles) => roles,
+            None => return Ok(ValidateCallbackResult::Invalid(
+                format!("Agent ({}) is not a countersigning participant", agent )
+            )),
+        };
+
+        if let Some(role) = required_roles.iter().find(|role| !roles.contains( role ) ) {
+            return Ok(ValidateCallbackResult::Invalid(
+                format!("Countersigning participant ({}) does not have role {}", agent, role.0 )
+            ));
+        }
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// Validate a countersigned [`Create`] of `T` against its countersigned entry
+///
+/// - the entry type must be `T`'s app entry def
+/// - the app entry (without the session data) must hash to the preflight's app entry hash
+/// - the author must be a participant and the action must follow their session chain top
+pub fn validate_countersigned_create<T,ET,ETU>(
+    create: &Create,
+    entry: &Entry,
+) -> ExternResult<ValidateCallbackResult>
+where
+    T: ScopedTypeConnector<ET,ETU>,
+    ScopedEntryDefIndex: for<'a> TryFrom<&'a ET, Error = WasmError>,
+{
+    let (session, app_bytes) = match entry {
+        Entry::CounterSign(session, app_bytes) => (session, app_bytes),
+        _ => return Ok(ValidateCallbackResult::Invalid(
+            "Expected a countersigned entry".to_string()
+        )),
+    };
+    let preflight = session.preflight_request();
+
+    match &create.entry_type {
+        EntryType::App(app_entry_def) if *app_entry_def == T::app_entry_def() => (),
+        _ => return Ok(ValidateCallbackResult::Invalid(
+            format!("Countersigned create has an unexpected entry type: {:?}", create.entry_type )
+        )),
+    }
+
+    let app_entry_hash = hash_entry( Entry::App( app_bytes.to_owned() ) )?;
+
+    if app_entry_hash != preflight.app_entry_hash {
+        return Ok(ValidateCallbackResult::Invalid(
+            format!(
+                "App entry hash ({}) does not match the preflight app entry hash ({})",
+                app_entry_hash, preflight.app_entry_hash,
+            )
+        ));
+    }
+
+    match participant_state( session, &create.author ) {
+        Some(state) if *state.chain_top() == create.prev_action => (),
+        Some(state) => return Ok(ValidateCallbackResult::Invalid(
+            format!(
+                "Countersigned create must follow the author's session chain top ({}); not '{}'",
+                state.chain_top(), create.prev_action,
+            )
+        )),
+        None => return Ok(ValidateCallbackResult::Invalid(
+            format!("Session has no response from the author ({})", create.author )
+        )),
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// Validate that every participant's action creates the same entry
+///
+/// Each action must be a [`Create`] by a participant and all entry hashes must be equal.  Every
+/// required signing agent must be covered, plus at least `minimum_optional_signing_agents` of the
+/// optional signing agents (when there are any).
+pub fn validate_participant_actions(
+    session: &CounterSigningSessionData,
+    actions: &[ActionHash],
+) -> ExternResult<ValidateCallbackResult> {
+    let preflight = session.preflight_request();
+    let mut authors = vec![];
+    let mut entry_hash = None;
+
+    for addr in actions {
+        let create = match summon_action( addr )?.hashed.content {
+            Action::Create(create) => create,
+            action => return Ok(ValidateCallbackResult::Invalid(
+                format!("Participant action ({}) is not a Create; found '{}'", addr, action.action_type() )
+            )),
+        };
+
+        if participant_roles( preflight, &create.author ).is_none() {
+            return Ok(ValidateCallbackResult::Invalid(
+                format!("Action ({}) author ({}) is not a countersigning participant", addr, create.author )
+            ));
+        }
+
+        match &entry_hash {
+            None => entry_hash = Some( create.entry_hash.to_owned() ),
+            Some(expected) if *expected != create.entry_hash => return Ok(ValidateCallbackResult::Invalid(
+                format!(
+                    "Participant action ({}) entry hash ({}) does not match the other participants ({})",
+                    addr, create.entry_hash, expected,
+                )
+            )),
+            Some(_) => (),
+        }
+
+        authors.push( create.author );
+    }
+
+    if let Some((agent, _)) = preflight.signing_agents.iter()
+        .find(|(agent, _)| !authors.contains( agent ) )
+    {
+        return Ok(ValidateCallbackResult::Invalid(
+            format!("Missing countersigned action from participant ({})", agent )
+        ));
+    }
+
+    if !preflight.optional_signing_agents.is_empty() {
+        let optional_count = preflight.optional_signing_agents.iter()
+            .filter(|(agent, _)| authors.contains( agent ) )
+            .count();
+
+        if optional_count < preflight.minimum_optional_signing_agents as usize {
+            return Ok(ValidateCallbackResult::Invalid(
+                format!(
+                    "Only {} optional participants countersigned; {} required",
+                    optional_count, preflight.minimum_optional_signing_agents,
+                )
+            ));
+        }
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
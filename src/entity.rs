@@ -0,0 +1,227 @@
+use core::convert::TryFrom;
+use hdi::prelude::{
+    ExternResult, WasmError,
+    Deserialize, Serialize,
+    ActionHash, EntryHash, AgentPubKey, Timestamp,
+    Record, Action,
+    ScopedEntryDefIndex,
+};
+use crate::{
+    guest_error,
+    trace_origin_root,
+    ScopedTypeConnector,
+    VersionedEntry,
+};
+#[cfg(feature = "hdk")]
+use crate::{
+    summon_action,
+    ForkPolicy, LatestTimestamp,
+};
+
+
+//
+// Entity Struct
+//
+/// A versioned app entry identified by its root [`Create`](hdi::prelude::Create) action
+///
+/// The `id` never changes; `action` and `address` point to the version that `content` was read
+/// from.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct Entity<T> {
+    /// Address of the root `Create` action
+    pub id: ActionHash,
+    /// Address of the action this version was read from
+    pub action: ActionHash,
+    /// Address of this version's entry
+    pub address: EntryHash,
+    /// Name of the entry types unit (eg. `"Post"`)
+    #[serde(rename = "type")]
+    pub type_name: String,
+    /// Deserialized app entry
+    pub content: T,
+    /// Author of the root `Create` action
+    pub author: AgentPubKey,
+    /// Timestamp of the root `Create` action
+    pub published_at: Timestamp,
+    /// Timestamp of the action this version was read from
+    pub last_updated: Timestamp,
+}
+
+impl<T> Entity<T> {
+    /// Create an [`Entity`] from any version's [`Record`] by tracing back to its root
+    ///
+    /// ##### Example: Basic Usage
+    /// ```ignore
+    /// use hdi::prelude::*;
+    /// use hdi_extensions::*;
+    ///
+    /// fn test(addr: ActionHash) -> ExternResult<()> {
+    ///     let record = summon_valid_record( &addr )?;
+    ///     let post : Entity<PostEntry> = Entity::try_from_record( &record )?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn try_from_record<ET,ETU>(record: &Record) -> ExternResult<Self>
+    where
+        T: ScopedTypeConnector<ET,ETU> + TryFrom<Record, Error = WasmError>,
+        ScopedEntryDefIndex: for<'a> TryFrom<&'a ET, Error = WasmError>,
+        ETU: core::fmt::Debug,
+    {
+        let (id, root) = trace_origin_root( record.action_address() )?;
+
+        Self::try_from_parts( id, &root, record )
+    }
+
+    /// Create an [`Entity`] from an already known root action and a version's [`Record`]
+    pub fn try_from_parts<ET,ETU>(id: ActionHash, root: &Action, record: &Record) -> ExternResult<Self>
+    where
+        T: ScopedTypeConnector<ET,ETU> + TryFrom<Record, Error = WasmError>,
+        ScopedEntryDefIndex: for<'a> TryFrom<&'a ET, Error = WasmError>,
+        ETU: core::fmt::Debug,
+    {
+        let content = T::try_from_record( record )?;
+
+        Self::from_parts( id, root, record, content, format!("{:?}", T::unit() ) )
+    }
+
+    /// Create an [`Entity`] from a [`VersionedEntry`] record, upgrading the content to the latest
+    /// version
+    ///
+    /// ##### Example: Basic Usage
+    /// ```ignore
+    /// use hdi::prelude::*;
+    /// use hdi_extensions::*;
+    ///
+    /// fn test(addr: ActionHash) -> ExternResult<()> {
+    ///     let record = summon_valid_record( &addr )?;
+    ///     let post : Entity<PostEntryV3> = Entity::try_from_versioned_record::<PostEntry,_,_>( &record )?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn try_from_versioned_record<V,ET,ETU>(record: &Record) -> ExternResult<Self>
+    where
+        V: VersionedEntry<Latest = T> + ScopedTypeConnector<ET,ETU> + TryFrom<Record, Error = WasmError>,
+        ScopedEntryDefIndex: for<'a> TryFrom<&'a ET, Error = WasmError>,
+        ETU: core::fmt::Debug,
+    {
+        let (id, root) = trace_origin_root( record.action_address() )?;
+        let content = V::try_from_record( record )?.into_latest()?;
+
+        Self::from_parts( id, &root, record, content, format!("{:?}", V::unit() ) )
+    }
+
+    fn from_parts(
+        id: ActionHash,
+        root: &Action,
+        record: &Record,
+        content: T,
+        type_name: String,
+    ) -> ExternResult<Self> {
+        if !matches!( root, Action::Create(_) ) {
+            Err(guest_error!(
+                format!("Entity ID ({}) must be a Create action; not '{}'", id, root.action_type() )
+            ))?
+        }
+
+        let address = record.action().entry_hash()
+            .ok_or(guest_error!(
+                format!("Action type ({}) does not contain an entry", record.action().action_type() )
+            ))?
+            .to_owned();
+
+        Ok(Entity {
+            id,
+            action: record.action_address().to_owned(),
+            address,
+            type_name,
+            content,
+            author: root.author().to_owned(),
+            published_at: root.timestamp(),
+            last_updated: record.action().timestamp(),
+        })
+    }
+}
+
+
+//
+// Version Resolution
+//
+/// Follow the updates of an action forward to the newest version
+///
+/// When an action has more than one update, the next step is chosen using [`LatestTimestamp`].
+#[cfg(feature = "hdk")]
+pub fn follow_updates(action_addr: &ActionHash) -> ExternResult<Record> {
+    follow_updates_with_policy( action_addr, &LatestTimestamp )
+}
+
+/// Follow the updates of an action forward using the given [`ForkPolicy`]
+#[cfg(feature = "hdk")]
+pub fn follow_updates_with_policy<P>(action_addr: &ActionHash, policy: &P) -> ExternResult<Record>
+where
+    P: ForkPolicy + ?Sized,
+{
+    use std::collections::BTreeSet;
+    use hdk::prelude::{ get_details, GetOptions, Details };
+
+    let mut visited = BTreeSet::new();
+    let mut current = action_addr.to_owned();
+
+    loop {
+        let details = match get_details( current.clone(), GetOptions::default() )? {
+            Some(Details::Record(details)) => details,
+            Some(_) => Err(guest_error!(
+                format!("Action address ({}) did not return record details", current )
+            ))?,
+            None => Err(guest_error!(
+                format!("Record not found for action address ({})", current )
+            ))?,
+        };
+
+        visited.insert( current );
+
+        match details.updates.into_iter()
+            .filter(|update| !visited.contains( &update.hashed.hash ) )
+            .max_by(|a, b| policy.compare( a, b ) )
+        {
+            Some(update) => current = update.hashed.hash,
+            None => return Ok( details.record ),
+        }
+    }
+}
+
+/// Get the newest version of an entity
+///
+/// ##### Example: Basic Usage
+/// ```ignore
+/// use hdk::prelude::*;
+/// use hdi_extensions::*;
+///
+/// fn test(id: ActionHash) -> ExternResult<()> {
+///     let post : Entity<PostEntry> = get_latest( &id )?;
+///     Ok(())
+/// }
+/// ```
+#[cfg(feature = "hdk")]
+pub fn get_latest<T,ET,ETU>(id: &ActionHash) -> ExternResult<Entity<T>>
+where
+    T: ScopedTypeConnector<ET,ETU> + TryFrom<Record, Error = WasmError>,
+    ScopedEntryDefIndex: for<'a> TryFrom<&'a ET, Error = WasmError>,
+    ETU: core::fmt::Debug,
+{
+    get_latest_with_policy( id, &LatestTimestamp )
+}
+
+/// Get the newest version of an entity using the given [`ForkPolicy`]
+#[cfg(feature = "hdk")]
+pub fn get_latest_with_policy<T,ET,ETU,P>(id: &ActionHash, policy: &P) -> ExternResult<Entity<T>>
+where
+    T: ScopedTypeConnector<ET,ETU> + TryFrom<Record, Error = WasmError>,
+    ScopedEntryDefIndex: for<'a> TryFrom<&'a ET, Error = WasmError>,
+    ETU: core::fmt::Debug,
+    P: ForkPolicy + ?Sized,
+{
+    let root = summon_action( id )?.hashed.content;
+    let latest = follow_updates_with_policy( id, policy )?;
+
+    Entity::try_from_parts( id.to_owned(), &root, &latest )
+}
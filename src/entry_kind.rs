@@ -0,0 +1,101 @@
+use hdi::prelude::{
+    ExternResult,
+    ActionHash,
+    Entry, EntryType, EntryCreationAction,
+    AppEntryDef, CapGrantEntry, CapClaimEntry,
+};
+use crate::{
+    guest_error,
+    summon_creation_action,
+    summon_entry,
+};
+
+
+//
+// Entry Kinds
+//
+/// The kind of entry that a creation action points to
+#[derive(Clone, Debug, PartialEq)]
+pub enum EntryKind {
+    /// An app entry with its zome, entry index and visibility
+    App(AppEntryDef),
+    /// An agent's public key entry
+    AgentPubKey,
+    /// A private capability grant
+    CapGrant,
+    /// A private capability claim
+    CapClaim,
+}
+
+impl From<EntryType> for EntryKind {
+    fn from(entry_type: EntryType) -> Self {
+        match entry_type {
+            EntryType::App(app_entry_def) => EntryKind::App( app_entry_def ),
+            EntryType::AgentPubKey => EntryKind::AgentPubKey,
+            EntryType::CapGrant => EntryKind::CapGrant,
+            EntryType::CapClaim => EntryKind::CapClaim,
+        }
+    }
+}
+
+/// Detect the [`EntryKind`] from a given [`Action`](hdi::prelude::Action)
+///
+/// Unlike [`detect_app_entry_def`](crate::detect_app_entry_def), system entry types are not an
+/// error.
+///
+/// ##### Example: Basic Usage
+/// ```
+/// # use hdi::prelude::*;
+/// # use hdi_extensions::*;
+///
+/// fn is_cap_grant(create: Create) -> bool {
+///     detect_entry_kind( &create ) == EntryKind::CapGrant
+/// }
+/// ```
+pub fn detect_entry_kind<A>(action: &A) -> EntryKind
+where
+    A: Into<EntryCreationAction> + Clone,
+{
+    let action : EntryCreationAction = action.to_owned().into();
+
+    action.entry_type().to_owned().into()
+}
+
+
+//
+// Capability Entries
+//
+// Capability entries are private, so these only succeed where the entry is held (eg. the author's
+// own source chain).
+fn summon_entry_of_kind(action_addr: &ActionHash, kind: EntryKind) -> ExternResult<Entry> {
+    let action = summon_creation_action( action_addr )?;
+    let found = detect_entry_kind( &action );
+
+    if found != kind {
+        Err(guest_error!(
+            "Action address ({}) is not a {:?} entry; found {:?}", action_addr, kind, found
+        ))?
+    }
+
+    Ok( summon_entry( action.entry_hash() )?.content )
+}
+
+/// Get the [`CapGrantEntry`] created by the given action
+pub fn summon_cap_grant(action_addr: &ActionHash) -> ExternResult<CapGrantEntry> {
+    match summon_entry_of_kind( action_addr, EntryKind::CapGrant )? {
+        Entry::CapGrant(cap_grant) => Ok( cap_grant ),
+        _ => Err(guest_error!(
+            "Entry for action address ({}) is not a CapGrant", action_addr
+        )),
+    }
+}
+
+/// Get the [`CapClaimEntry`] created by the given action
+pub fn summon_cap_claim(action_addr: &ActionHash) -> ExternResult<CapClaimEntry> {
+    match summon_entry_of_kind( action_addr, EntryKind::CapClaim )? {
+        Entry::CapClaim(cap_claim) => Ok( cap_claim ),
+        _ => Err(guest_error!(
+            "Entry for action address ({}) is not a CapClaim", action_addr
+        )),
+    }
+}
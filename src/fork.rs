@@ -0,0 +1,180 @@
+use core::cmp::Ordering;
+use std::collections::{ BTreeMap, BTreeSet };
+use hdi::prelude::{
+    ExternResult,
+    Deserialize, Serialize,
+    ActionHash, AgentPubKey,
+    Action, SignedActionHashed,
+};
+use crate::guest_error;
+
+
+//
+// Fork Policies
+//
+/// Decides which of several sibling updates continues the canonical branch
+///
+/// Implementations must define a total order so that every peer resolves the same head.  Closures
+/// of the form `Fn(&SignedActionHashed, &SignedActionHashed) -> Ordering` are also policies.
+pub trait ForkPolicy {
+    /// Order two sibling updates so that the preferred update is the greatest
+    fn compare(&self, a: &SignedActionHashed, b: &SignedActionHashed) -> Ordering;
+}
+
+impl<F> ForkPolicy for F
+where
+    F: Fn(&SignedActionHashed, &SignedActionHashed) -> Ordering,
+{
+    fn compare(&self, a: &SignedActionHashed, b: &SignedActionHashed) -> Ordering {
+        self( a, b )
+    }
+}
+
+/// Prefer the lowest action hash
+///
+/// This is the final tie-breaker for every other policy in this module.
+#[derive(Clone, Debug, Default)]
+pub struct LowestHash;
+
+impl ForkPolicy for LowestHash {
+    fn compare(&self, a: &SignedActionHashed, b: &SignedActionHashed) -> Ordering {
+        b.hashed.hash.get_raw_39().cmp( a.hashed.hash.get_raw_39() )
+    }
+}
+
+/// Prefer the newest timestamp, then the lowest action hash
+#[derive(Clone, Debug, Default)]
+pub struct LatestTimestamp;
+
+impl ForkPolicy for LatestTimestamp {
+    fn compare(&self, a: &SignedActionHashed, b: &SignedActionHashed) -> Ordering {
+        a.hashed.content.timestamp().cmp( &b.hashed.content.timestamp() )
+            .then_with(|| LowestHash.compare( a, b ) )
+    }
+}
+
+/// Prefer authors earlier in the list, then the newest timestamp, then the lowest action hash
+///
+/// Authors that are not in the list rank below every listed author.
+#[derive(Clone, Debug, Default)]
+pub struct AuthorPriority(pub Vec<AgentPubKey>);
+
+impl AuthorPriority {
+    fn rank(&self, author: &AgentPubKey) -> usize {
+        self.0.iter()
+            .position(|agent| agent == author)
+            .unwrap_or( self.0.len() )
+    }
+}
+
+impl ForkPolicy for AuthorPriority {
+    fn compare(&self, a: &SignedActionHashed, b: &SignedActionHashed) -> Ordering {
+        self.rank( b.hashed.content.author() ).cmp( &self.rank( a.hashed.content.author() ) )
+            .then_with(|| LatestTimestamp.compare( a, b ) )
+    }
+}
+
+
+//
+// Fork Resolution
+//
+/// An action that has more than one update
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct Fork {
+    /// The action being updated
+    pub original: ActionHash,
+    /// Updates of `original` ordered from most to least preferred
+    pub branches: Vec<ActionHash>,
+}
+
+/// Result of [`resolve_head`]
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct ForkResolution {
+    /// The canonical latest action
+    pub head: ActionHash,
+    /// The canonical path from the root to `head` (inclusive)
+    pub path: Vec<ActionHash>,
+    /// Every fork detected in the candidates, ordered by the original's address
+    pub forks: Vec<Fork>,
+}
+
+impl ForkResolution {
+    /// Check if any forks were detected
+    pub fn has_forks(&self) -> bool {
+        !self.forks.is_empty()
+    }
+}
+
+/// Determine the canonical head of an update tree
+///
+/// Starting at `root`, the preferred update (according to `policy`) is followed at each step until
+/// an action with no updates is reached.  Candidates that are not [`Action::Update`]s are rejected
+/// and duplicates are ignored.
+///
+/// ##### Example: Basic Usage
+/// ```ignore
+/// use hdi::prelude::*;
+/// use hdi_extensions::*;
+///
+/// fn test(root: ActionHash, updates: Vec<SignedActionHashed>) -> ExternResult<()> {
+///     let resolution = resolve_head( &root, &updates, &LatestTimestamp )?;
+///     if resolution.has_forks() {
+///         debug!("Forks detected: {:?}", resolution.forks );
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn resolve_head<P>(
+    root: &ActionHash,
+    candidates: &[SignedActionHashed],
+    policy: &P,
+) -> ExternResult<ForkResolution>
+where
+    P: ForkPolicy + ?Sized,
+{
+    let mut seen = BTreeSet::new();
+    let mut children : BTreeMap<ActionHash, Vec<&SignedActionHashed>> = BTreeMap::new();
+
+    for candidate in candidates {
+        let original = match &candidate.hashed.content {
+            Action::Update(update) => update.original_action_address.to_owned(),
+            action => Err(guest_error!(
+                format!("Fork candidate ({}) must be an Update action; not '{}'", candidate.hashed.hash, action.action_type() )
+            ))?,
+        };
+
+        if seen.insert( candidate.hashed.hash.to_owned() ) {
+            children.entry( original ).or_default().push( candidate );
+        }
+    }
+
+    for siblings in children.values_mut() {
+        siblings.sort_by(|a, b| policy.compare( b, a ) );
+    }
+
+    let forks = children.iter()
+        .filter(|(_, siblings)| siblings.len() > 1 )
+        .map(|(original, siblings)| Fork {
+            original: original.to_owned(),
+            branches: siblings.iter()
+                .map(|update| update.hashed.hash.to_owned() )
+                .collect(),
+        })
+        .collect();
+
+    let mut path = vec![ root.to_owned() ];
+    let mut visited = BTreeSet::from([ root.to_owned() ]);
+
+    while let Some(next) = children.get( path.last().unwrap() )
+        .and_then(|siblings| siblings.iter().find(|update| !visited.contains( &update.hashed.hash ) ) )
+    {
+        visited.insert( next.hashed.hash.to_owned() );
+        path.push( next.hashed.hash.to_owned() );
+    }
+
+    Ok(ForkResolution {
+        head: path.last().unwrap().to_owned(),
+        path,
+        forks,
+    })
+}
@@ -0,0 +1,497 @@
+use core::fmt;
+use hdi::prelude::{
+    ExternResult,
+    Deserialize, Serialize,
+    ActionHash, EntryHash, ExternalHash, AgentPubKey, DnaHash,
+    AnyDhtHash, AnyLinkableHash,
+};
+use base64::{
+    Engine,
+    engine::general_purpose::URL_SAFE_NO_PAD,
+};
+use holo_hash::{
+    hash_type,
+    encode::holo_dht_location_bytes,
+};
+use crate::guest_error;
+
+
+const HASH_PREFIX_LEN : usize = 3;
+const HASH_CORE_LEN : usize = 32;
+const HASH_LOC_LEN : usize = 4;
+const HASH_FULL_LEN : usize = HASH_PREFIX_LEN + HASH_CORE_LEN + HASH_LOC_LEN;
+// 'u' + base64 of 39 bytes
+const HASH_STRING_LEN : usize = 53;
+
+
+//
+// Hash Kind
+//
+/// The primitive type of a HoloHash as declared by its 3-byte prefix
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum HashKind {
+    /// `uhCkk` - [`ActionHash`]
+    Action,
+    /// `uhCEk` - [`EntryHash`]
+    Entry,
+    /// `uhCAk` - [`AgentPubKey`]
+    Agent,
+    /// `uhC8k` - [`ExternalHash`]
+    External,
+    /// `uhC0k` - [`DnaHash`]
+    Dna,
+    /// `uhCQk` - [`DhtOpHash`](holo_hash::DhtOpHash)
+    DhtOp,
+    /// `uhCok` - [`WasmHash`](holo_hash::WasmHash)
+    Wasm,
+}
+
+impl HashKind {
+    /// Every known kind
+    pub const ALL : [HashKind; 7] = [
+        HashKind::Action,
+        HashKind::Entry,
+        HashKind::Agent,
+        HashKind::External,
+        HashKind::Dna,
+        HashKind::DhtOp,
+        HashKind::Wasm,
+    ];
+
+    /// Get the 3-byte prefix for this kind
+    pub fn prefix(&self) -> [u8; 3] {
+        match self {
+            HashKind::Action => [ 0x84, 0x29, 0x24 ],
+            HashKind::Entry => [ 0x84, 0x21, 0x24 ],
+            HashKind::Agent => [ 0x84, 0x20, 0x24 ],
+            HashKind::External => [ 0x84, 0x2f, 0x24 ],
+            HashKind::Dna => [ 0x84, 0x2d, 0x24 ],
+            HashKind::DhtOp => [ 0x84, 0x24, 0x24 ],
+            HashKind::Wasm => [ 0x84, 0x2a, 0x24 ],
+        }
+    }
+
+    /// Determine the kind from a 3-byte prefix
+    pub fn from_prefix(prefix: &[u8]) -> Option<Self> {
+        Self::ALL.into_iter()
+            .find(|kind| kind.prefix() == prefix )
+    }
+
+    /// Human readable name used in error messages
+    pub fn name(&self) -> &'static str {
+        match self {
+            HashKind::Action => "Action",
+            HashKind::Entry => "Entry",
+            HashKind::Agent => "Agent",
+            HashKind::External => "External",
+            HashKind::Dna => "DNA",
+            HashKind::DhtOp => "DHT Op",
+            HashKind::Wasm => "Wasm",
+        }
+    }
+
+    /// Check if this kind can be used as an [`AnyLinkableHash`]
+    ///
+    /// Agent keys are linkable as entry hashes.
+    pub fn is_linkable(&self) -> bool {
+        matches!( self, HashKind::Action | HashKind::Entry | HashKind::Agent | HashKind::External )
+    }
+
+    /// Check if this kind can be used as an [`AnyDhtHash`]
+    ///
+    /// Agent keys are DHT addressable as entry hashes.
+    pub fn is_dht(&self) -> bool {
+        matches!( self, HashKind::Action | HashKind::Entry | HashKind::Agent )
+    }
+}
+
+impl fmt::Display for HashKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name() )
+    }
+}
+
+
+//
+// Decoded Hash
+//
+/// A HoloHash that has been decoded and checked, but not yet converted to a typed hash
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecodedHash {
+    kind: HashKind,
+    raw_39: Vec<u8>,
+}
+
+impl DecodedHash {
+    /// Decode a `u`-prefixed base64 HoloHash string in a single pass
+    ///
+    /// The prefix determines the [`HashKind`] and the DHT location bytes are verified against the
+    /// hash core.
+    ///
+    /// ##### Example: Basic Usage
+    /// ```
+    /// # use hdi::prelude::*;
+    /// # use hdi_extensions::*;
+    ///
+    /// fn test(input: String) -> ExternResult<()> {
+    ///     let decoded = DecodedHash::try_from_string( &input )?;
+    ///     if decoded.kind() == HashKind::Dna {
+    ///         Err(guest_error!(format!("Unexpected DNA hash")))?
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn try_from_string(input: &str) -> ExternResult<Self> {
+        // Decoded here instead of with 'holo_hash_decode_unchecked' so that checksum problems are
+        // reported precisely by 'try_from_checked_bytes'
+        let encoded = input.strip_prefix("u")
+            .ok_or(guest_error!(
+                "String '{}' is not a valid HoloHash: missing 'u' prefix", input
+            ))?;
+        let raw_39 = URL_SAFE_NO_PAD.decode( encoded )
+            .map_err(|err| guest_error!(
+                "String '{}' is not a valid HoloHash: {}", input, err
+            ))?;
+
+        Self::try_from_checked_bytes( raw_39 )
+            .map_err(|msg| guest_error!(
+                format!("String '{}' is valid base64 but has {}", input, msg )
+            ))
+    }
+
+    /// Decode raw hash bytes
+    ///
+    /// Accepted layouts are
+    /// - 39 bytes - prefix, core and location
+    /// - 35 bytes - prefix and core
+    /// - 36 bytes - core and location
+    /// - 32 bytes - core only
+    ///
+    /// When the prefix is missing, `kinds` must contain exactly one kind so that the prefix can be
+    /// implied.  Missing location bytes are calculated and present location bytes are verified.
+    pub fn try_from_bytes(bytes: &[u8], kinds: &[HashKind]) -> ExternResult<Self> {
+        Self::try_from_any_bytes( bytes, kinds )
+            .map_err(|msg| guest_error!(
+                format!("Hash bytes are not a valid HoloHash: {}", msg )
+            ))
+    }
+
+    /// Decode a hex string (optionally prefixed with `0x`) using the layouts from
+    /// [`DecodedHash::try_from_bytes`]
+    pub fn try_from_hex(input: &str, kinds: &[HashKind]) -> ExternResult<Self> {
+        let bytes = decode_hex( input.strip_prefix("0x").unwrap_or( input ) )
+            .map_err(|msg| guest_error!(
+                format!("String '{}' is not valid hex: {}", input, msg )
+            ))?;
+
+        Self::try_from_any_bytes( &bytes, kinds )
+            .map_err(|msg| guest_error!(
+                format!("String '{}' is valid hex but has {}", input, msg )
+            ))
+    }
+
+    /// Decode a base64url string using the layouts from [`DecodedHash::try_from_bytes`]
+    ///
+    /// The canonical `u` prefix and trailing padding are optional.
+    pub fn try_from_base64(input: &str, kinds: &[HashKind]) -> ExternResult<Self> {
+        let encoded = match input.len() {
+            HASH_STRING_LEN => input.strip_prefix("u").unwrap_or( input ),
+            _ => input,
+        };
+        let bytes = URL_SAFE_NO_PAD.decode( encoded.trim_end_matches('=') )
+            .map_err(|err| guest_error!(
+                format!("String '{}' is not valid base64url: {}", input, err )
+            ))?;
+
+        Self::try_from_any_bytes( &bytes, kinds )
+            .map_err(|msg| guest_error!(
+                format!("String '{}' is valid base64 but has {}", input, msg )
+            ))
+    }
+
+    fn try_from_any_bytes(bytes: &[u8], kinds: &[HashKind]) -> Result<Self, String> {
+        let implied_prefix = || match kinds {
+            [ kind ] => Ok( kind.prefix() ),
+            _ => Err(format!("no type prefix and the expected type is ambiguous; expected {} hash", describe_kinds( kinds ) )),
+        };
+
+        let raw_39 = match bytes.len() {
+            HASH_FULL_LEN => bytes.to_vec(),
+            len if len == HASH_PREFIX_LEN + HASH_CORE_LEN => [
+                bytes,
+                &holo_dht_location_bytes( &bytes[HASH_PREFIX_LEN..] )[..],
+            ].concat(),
+            len if len == HASH_CORE_LEN + HASH_LOC_LEN => [
+                &implied_prefix()?[..],
+                bytes,
+            ].concat(),
+            HASH_CORE_LEN => [
+                &implied_prefix()?[..],
+                bytes,
+                &holo_dht_location_bytes( bytes )[..],
+            ].concat(),
+            len => Err(format!("a length of {} bytes; expected 32, 35, 36 or 39", len ))?,
+        };
+
+        Self::try_from_checked_bytes( raw_39 )
+    }
+
+    fn try_from_checked_bytes(raw_39: Vec<u8>) -> Result<Self, String> {
+        if raw_39.len() != HASH_FULL_LEN {
+            Err(format!("a length of {} bytes; expected {}", raw_39.len(), HASH_FULL_LEN ))?
+        }
+
+        let kind = HashKind::from_prefix( &raw_39[..HASH_PREFIX_LEN] )
+            .ok_or(format!("an unknown HoloHash prefix {:?}", &raw_39[..HASH_PREFIX_LEN] ))?;
+
+        let core = &raw_39[HASH_PREFIX_LEN..HASH_PREFIX_LEN + HASH_CORE_LEN];
+        if holo_dht_location_bytes( core ) != raw_39[HASH_PREFIX_LEN + HASH_CORE_LEN..] {
+            Err(format!("DHT location bytes that do not match the {} hash core", kind ))?
+        }
+
+        Ok(DecodedHash { kind, raw_39 })
+    }
+
+    /// The kind declared by the hash prefix
+    pub fn kind(&self) -> HashKind {
+        self.kind
+    }
+
+    /// Prefix, core and location bytes
+    pub fn get_raw_39(&self) -> &[u8] {
+        &self.raw_39
+    }
+
+    /// Core and location bytes
+    pub fn get_raw_36(&self) -> &[u8] {
+        &self.raw_39[HASH_PREFIX_LEN..]
+    }
+
+    /// Convert to an [`ActionHash`] if the kind matches
+    pub fn to_action_hash(&self) -> Option<ActionHash> {
+        match self.kind {
+            HashKind::Action => Some( ActionHash::from_raw_36( self.get_raw_36().to_vec() ) ),
+            _ => None,
+        }
+    }
+
+    /// Convert to an [`EntryHash`] if the kind matches
+    pub fn to_entry_hash(&self) -> Option<EntryHash> {
+        match self.kind {
+            HashKind::Entry => Some( EntryHash::from_raw_36( self.get_raw_36().to_vec() ) ),
+            _ => None,
+        }
+    }
+
+    /// Convert to an [`ExternalHash`] if the kind matches
+    pub fn to_external_hash(&self) -> Option<ExternalHash> {
+        match self.kind {
+            HashKind::External => Some( ExternalHash::from_raw_36( self.get_raw_36().to_vec() ) ),
+            _ => None,
+        }
+    }
+
+    /// Convert to an [`AgentPubKey`] if the kind matches
+    pub fn to_agent_pubkey(&self) -> Option<AgentPubKey> {
+        match self.kind {
+            HashKind::Agent => Some( AgentPubKey::from_raw_36( self.get_raw_36().to_vec() ) ),
+            _ => None,
+        }
+    }
+
+    /// Convert to a [`DnaHash`] if the kind matches
+    pub fn to_dna_hash(&self) -> Option<DnaHash> {
+        match self.kind {
+            HashKind::Dna => Some( DnaHash::from_raw_36( self.get_raw_36().to_vec() ) ),
+            _ => None,
+        }
+    }
+
+    /// Convert to an [`AnyLinkableHash`] if the kind is linkable
+    pub fn to_any_linkable_hash(&self) -> Option<AnyLinkableHash> {
+        let hash_type = match self.kind {
+            HashKind::Action => hash_type::AnyLinkable::Action,
+            HashKind::Entry | HashKind::Agent => hash_type::AnyLinkable::Entry,
+            HashKind::External => hash_type::AnyLinkable::External,
+            _ => return None,
+        };
+
+        Some( AnyLinkableHash::from_raw_36_and_type( self.get_raw_36().to_vec(), hash_type ) )
+    }
+
+    /// Convert to an [`AnyDhtHash`] if the kind is DHT addressable
+    pub fn to_any_dht_hash(&self) -> Option<AnyDhtHash> {
+        let hash_type = match self.kind {
+            HashKind::Action => hash_type::AnyDht::Action,
+            HashKind::Entry | HashKind::Agent => hash_type::AnyDht::Entry,
+            _ => return None,
+        };
+
+        Some( AnyDhtHash::from_raw_36_and_type( self.get_raw_36().to_vec(), hash_type ) )
+    }
+}
+
+/// Detect the [`HashKind`] of a HoloHash string
+pub fn detect_hash_kind(input: &str) -> ExternResult<HashKind> {
+    Ok( DecodedHash::try_from_string( input )?.kind() )
+}
+
+
+//
+// Typed Decoding
+//
+/// A hash type that can be produced from a [`DecodedHash`]
+pub trait DecodableHash : Sized {
+    /// The kinds that can be converted into this type
+    const KINDS: &'static [HashKind];
+
+    /// Convert if the decoded kind is one of [`DecodableHash::KINDS`]
+    fn from_decoded(decoded: &DecodedHash) -> Option<Self>;
+}
+
+macro_rules! decodable_hash {
+    ( $hash_type:ident, [ $($kind:ident),+ ], $method:ident ) => {
+        impl DecodableHash for $hash_type {
+            const KINDS: &'static [HashKind] = &[ $(HashKind::$kind),+ ];
+
+            fn from_decoded(decoded: &DecodedHash) -> Option<Self> {
+                decoded.$method()
+            }
+        }
+    };
+}
+
+decodable_hash!( ActionHash, [ Action ], to_action_hash );
+decodable_hash!( EntryHash, [ Entry ], to_entry_hash );
+decodable_hash!( ExternalHash, [ External ], to_external_hash );
+decodable_hash!( AgentPubKey, [ Agent ], to_agent_pubkey );
+decodable_hash!( DnaHash, [ Dna ], to_dna_hash );
+decodable_hash!( AnyLinkableHash, [ Action, Entry, Agent, External ], to_any_linkable_hash );
+decodable_hash!( AnyDhtHash, [ Action, Entry, Agent ], to_any_dht_hash );
+
+fn convert_decoded<T>(decoded: DecodedHash, subject: String) -> ExternResult<T>
+where
+    T: DecodableHash,
+{
+    T::from_decoded( &decoded )
+        .ok_or(guest_error!(
+            format!("{} has {} hash prefix; expected {} hash", subject, describe_kinds( &[ decoded.kind() ] ), describe_kinds( T::KINDS ) )
+        ))
+}
+
+/// Decode a `u`-prefixed base64 HoloHash string into the expected hash type
+pub fn decode_hash_string<T>(input: &str) -> ExternResult<T>
+where
+    T: DecodableHash,
+{
+    convert_decoded(
+        DecodedHash::try_from_string( input )?,
+        format!("String '{}' is valid base64 but", input ),
+    )
+}
+
+/// Decode raw bytes into the expected hash type (see [`DecodedHash::try_from_bytes`])
+pub fn decode_hash_bytes<T>(bytes: &[u8]) -> ExternResult<T>
+where
+    T: DecodableHash,
+{
+    convert_decoded(
+        DecodedHash::try_from_bytes( bytes, T::KINDS )?,
+        "Hash bytes are valid but".to_string(),
+    )
+}
+
+/// Decode a hex string into the expected hash type (see [`DecodedHash::try_from_hex`])
+pub fn decode_hash_hex<T>(input: &str) -> ExternResult<T>
+where
+    T: DecodableHash,
+{
+    convert_decoded(
+        DecodedHash::try_from_hex( input, T::KINDS )?,
+        format!("String '{}' is valid hex but", input ),
+    )
+}
+
+/// Decode a base64url string into the expected hash type (see [`DecodedHash::try_from_base64`])
+///
+/// ##### Example: Basic Usage
+/// ```
+/// # use hdi::prelude::*;
+/// # use hdi_extensions::*;
+///
+/// fn test(input: String) -> ExternResult<()> {
+///     // Accepts 'uhCkk...' as well as the same hash with the 'uhCkk' prefix stripped
+///     let action_hash : ActionHash = decode_hash_base64( &input )?;
+///     Ok(())
+/// }
+/// ```
+pub fn decode_hash_base64<T>(input: &str) -> ExternResult<T>
+where
+    T: DecodableHash,
+{
+    convert_decoded(
+        DecodedHash::try_from_base64( input, T::KINDS )?,
+        format!("String '{}' is valid base64 but", input ),
+    )
+}
+
+/// Decode raw bytes, hex or base64url into any [`DecodableHash`]
+///
+/// ##### Example: Basic Usage
+/// ```
+/// # use hdi::prelude::*;
+/// # use hdi_extensions::*;
+///
+/// fn test(input: String) -> ExternResult<()> {
+///     let agent = AgentPubKey::try_from_hex( &input )?;
+///     Ok(())
+/// }
+/// ```
+pub trait DecodableHashTransformer : DecodableHash {
+    /// Decode raw bytes (see [`DecodedHash::try_from_bytes`])
+    fn try_from_bytes(bytes: &[u8]) -> ExternResult<Self> {
+        decode_hash_bytes( bytes )
+    }
+    /// Decode a hex string (see [`DecodedHash::try_from_hex`])
+    fn try_from_hex(input: &str) -> ExternResult<Self> {
+        decode_hash_hex( input )
+    }
+    /// Decode a base64url string (see [`DecodedHash::try_from_base64`])
+    fn try_from_base64(input: &str) -> ExternResult<Self> {
+        decode_hash_base64( input )
+    }
+}
+
+impl<T> DecodableHashTransformer for T
+where
+    T: DecodableHash,
+{}
+
+/// Format a list of kinds for error messages (eg. `"an Action, Entry or Agent"`)
+pub(crate) fn describe_kinds(kinds: &[HashKind]) -> String {
+    let names : Vec<&str> = kinds.iter().map(|kind| kind.name() ).collect();
+    let list = match names.split_last() {
+        Some((last, [])) => last.to_string(),
+        Some((last, rest)) => format!("{} or {}", rest.join(", "), last ),
+        None => "no".to_string(),
+    };
+
+    match list.chars().next() {
+        Some('A' | 'E' | 'I' | 'O' | 'U') => format!("an {}", list ),
+        _ => format!("a {}", list ),
+    }
+}
+
+fn decode_hex(input: &str) -> Result<Vec<u8>, String> {
+    if !input.len().is_multiple_of(2) {
+        Err(format!("odd number of digits ({})", input.len() ))?
+    }
+
+    (0..input.len())
+        .step_by(2)
+        .map(|i| input.get(i..i + 2)
+             .and_then(|pair| u8::from_str_radix( pair, 16 ).ok() )
+             .ok_or(format!("invalid hex digits at position {}", i ))
+        )
+        .collect()
+}
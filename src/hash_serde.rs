@@ -0,0 +1,191 @@
+//! Serde adapters for hash fields that accept either a HoloHash string or raw bytes
+//!
+//! Serialization is unchanged (raw bytes), so msgpack clients see the same wire format while JSON
+//! speaking tooling can send the `u`-prefixed base64 string.
+//!
+//! ##### Example: Basic Usage
+//! ```
+//! # use hdi::prelude::*;
+//!
+//! #[derive(Clone, Serialize, Deserialize, Debug)]
+//! pub struct GetPostInput {
+//!     #[serde(with = "hdi_extensions::hash_serde::action_hash")]
+//!     pub id: ActionHash,
+//!     #[serde(with = "hdi_extensions::hash_serde::any_linkable_hash")]
+//!     pub base: AnyLinkableHash,
+//!     #[serde(default, with = "hdi_extensions::hash_serde::agent_pubkey::option")]
+//!     pub author: Option<AgentPubKey>,
+//! }
+//! ```
+use core::fmt;
+use core::marker::PhantomData;
+use serde::{
+    Serialize, Serializer, Deserializer,
+    de::{ self, Visitor, SeqAccess },
+};
+use hdi::prelude::{
+    WasmError, WasmErrorInner,
+    ActionHash, EntryHash, ExternalHash, AgentPubKey, DnaHash,
+    AnyDhtHash, AnyLinkableHash,
+};
+use crate::{
+    DecodableHash,
+    decode_hash_string,
+    decode_hash_bytes,
+};
+
+
+fn error_message(error: WasmError) -> String {
+    match error.error {
+        WasmErrorInner::Guest(msg) => msg,
+        other => format!("{:?}", other ),
+    }
+}
+
+struct HashVisitor<T>(PhantomData<T>);
+
+impl<'de, T> Visitor<'de> for HashVisitor<T>
+where
+    T: DecodableHash,
+{
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a HoloHash string or bytes")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<T, E>
+    where
+        E: de::Error,
+    {
+        decode_hash_string( value )
+            .map_err(|err| E::custom( error_message( err ) ) )
+    }
+
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<T, E>
+    where
+        E: de::Error,
+    {
+        decode_hash_bytes( value )
+            .map_err(|err| E::custom( error_message( err ) ) )
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<T, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut bytes = Vec::with_capacity( seq.size_hint().unwrap_or(39) );
+
+        while let Some(byte) = seq.next_element::<u8>()? {
+            bytes.push( byte );
+        }
+
+        self.visit_bytes( &bytes )
+    }
+}
+
+/// Deserialize any [`DecodableHash`] from a HoloHash string, bytes or a sequence of bytes
+pub fn deserialize_hash<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DecodableHash,
+{
+    deserializer.deserialize_any( HashVisitor( PhantomData ) )
+}
+
+struct OptionalHashVisitor<T>(PhantomData<T>);
+
+impl<'de, T> Visitor<'de> for OptionalHashVisitor<T>
+where
+    T: DecodableHash,
+{
+    type Value = Option<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "null, a HoloHash string or bytes")
+    }
+
+    fn visit_none<E>(self) -> Result<Option<T>, E>
+    where
+        E: de::Error,
+    {
+        Ok( None )
+    }
+
+    fn visit_unit<E>(self) -> Result<Option<T>, E>
+    where
+        E: de::Error,
+    {
+        Ok( None )
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_hash( deserializer ).map( Some )
+    }
+}
+
+/// Deserialize an optional [`DecodableHash`] (see [`deserialize_hash`])
+pub fn deserialize_optional_hash<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DecodableHash,
+{
+    deserializer.deserialize_option( OptionalHashVisitor( PhantomData ) )
+}
+
+macro_rules! hash_adapter {
+    ( $module:ident, $hash_type:ident ) => {
+        #[doc = concat!("Use with `#[serde(with = \"hdi_extensions::hash_serde::", stringify!($module), "\")]` on a [`", stringify!($hash_type), "`] field")]
+        pub mod $module {
+            use super::*;
+
+            #[doc = concat!("Serialize a [`", stringify!($hash_type), "`] as usual")]
+            pub fn serialize<S>(hash: &$hash_type, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                hash.serialize( serializer )
+            }
+
+            #[doc = concat!("Deserialize a [`", stringify!($hash_type), "`] from a string or bytes")]
+            pub fn deserialize<'de, D>(deserializer: D) -> Result<$hash_type, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                deserialize_hash( deserializer )
+            }
+
+            #[doc = concat!("Use with `#[serde(default, with = \"hdi_extensions::hash_serde::", stringify!($module), "::option\")]` on an `Option<", stringify!($hash_type), ">` field")]
+            pub mod option {
+                use super::*;
+
+                #[doc = concat!("Serialize an `Option<", stringify!($hash_type), ">` as usual")]
+                pub fn serialize<S>(hash: &Option<$hash_type>, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    S: Serializer,
+                {
+                    hash.serialize( serializer )
+                }
+
+                #[doc = concat!("Deserialize an `Option<", stringify!($hash_type), ">` from null, a string or bytes")]
+                pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<$hash_type>, D::Error>
+                where
+                    D: Deserializer<'de>,
+                {
+                    deserialize_optional_hash( deserializer )
+                }
+            }
+        }
+    };
+}
+
+hash_adapter!( action_hash, ActionHash );
+hash_adapter!( entry_hash, EntryHash );
+hash_adapter!( external_hash, ExternalHash );
+hash_adapter!( agent_pubkey, AgentPubKey );
+hash_adapter!( dna_hash, DnaHash );
+hash_adapter!( any_linkable_hash, AnyLinkableHash );
+hash_adapter!( any_dht_hash, AnyDhtHash );
@@ -0,0 +1,219 @@
+use hdi::prelude::{
+    ExternResult,
+    Deserialize, Serialize,
+    AnyLinkableHash, AgentPubKey, Timestamp, LinkTag,
+    Entry,
+};
+use holo_hash::AnyLinkableHashPrimitive;
+use crate::{
+    guest_error,
+    summon_entry,
+    HashKind,
+    hash_kind::describe_kinds,
+};
+
+
+/// Determine the [`HashKind`] of an [`AnyLinkableHash`]
+///
+/// Agent keys are indistinguishable from entry hashes once they are any-linkable, so they are
+/// reported as [`HashKind::Entry`].
+pub fn linkable_hash_kind(hash: &AnyLinkableHash) -> HashKind {
+    match hash.to_owned().into_primitive() {
+        AnyLinkableHashPrimitive::Action(_) => HashKind::Action,
+        AnyLinkableHashPrimitive::Entry(_) => HashKind::Entry,
+        AnyLinkableHashPrimitive::External(_) => HashKind::External,
+    }
+}
+
+/// Expect an [`AnyLinkableHash`] to be one of the given kinds
+///
+/// Agent keys are linked as entry hashes, so when [`HashKind::Agent`] is allowed but
+/// [`HashKind::Entry`] is not, an entry hash is summoned and must be an
+/// [`Entry::Agent`](hdi::prelude::Entry::Agent).  When both are allowed, any entry hash passes as
+/// [`HashKind::Entry`].
+pub fn require_linkable_kind(
+    name: &str,
+    hash: &AnyLinkableHash,
+    kinds: &[HashKind],
+) -> ExternResult<HashKind> {
+    let kind = linkable_hash_kind( hash );
+
+    if kinds.contains( &kind ) {
+        return Ok( kind );
+    }
+
+    if let (true, Some(entry_hash)) = ( kinds.contains( &HashKind::Agent ), hash.to_owned().into_entry_hash() ) {
+        return match summon_entry( &entry_hash )?.content {
+            Entry::Agent(_) => Ok( HashKind::Agent ),
+            _ => Err(guest_error!(
+                format!("Link {} must be {} hash; not entry '{}'", name, describe_kinds( kinds ), hash )
+            )),
+        };
+    }
+
+    Err(guest_error!(
+        format!("Link {} must be {} hash; not '{}'", name, describe_kinds( kinds ), hash )
+    ))
+}
+
+
+//
+// Standard Inputs
+//
+/// Input for getting links based on direction (ignoring type/tag)
+///
+/// Hashes can be sent as bytes or as HoloHash strings (see [`hash_serde`](crate::hash_serde)).
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct LinkDirectionInput {
+    #[serde(with = "crate::hash_serde::any_linkable_hash")]
+    pub base: AnyLinkableHash,
+    #[serde(with = "crate::hash_serde::any_linkable_hash")]
+    pub target: AnyLinkableHash,
+}
+
+impl LinkDirectionInput {
+    /// Expect the base to be one of the given kinds
+    pub fn require_base_kind(&self, kinds: &[HashKind]) -> ExternResult<HashKind> {
+        require_linkable_kind( "base", &self.base, kinds )
+    }
+
+    /// Expect the target to be one of the given kinds
+    pub fn require_target_kind(&self, kinds: &[HashKind]) -> ExternResult<HashKind> {
+        require_linkable_kind( "target", &self.target, kinds )
+    }
+}
+
+/// Input for querying links from a base with optional filters
+///
+/// `LT` is the zome's link types enum (or any serializable link type identifier).  Every filter
+/// is optional and missing fields deserialize as `None`.
+///
+/// ##### Example: Basic Usage
+/// ```ignore
+/// use hdk::prelude::*;
+/// use hdi_extensions::*;
+///
+/// #[hdk_extern]
+/// fn get_posts(input: LinkQueryInput<LinkTypes>) -> ExternResult<Vec<Link>> {
+///     input.check()?;
+///     input.require_base_kind( &[ HashKind::Agent ] )?;
+///
+///     let links = get_links(
+///         GetLinksInputBuilder::try_new( input.base.clone(), input.link_type.unwrap_or(LinkTypes::Post) )?.build()
+///     )?;
+///
+///     Ok( links.into_iter()
+///         .filter(|link| input.matches( &link.tag, &link.author, &link.timestamp ) )
+///         .collect() )
+/// }
+/// ```
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct LinkQueryInput<LT> {
+    #[serde(with = "crate::hash_serde::any_linkable_hash")]
+    pub base: AnyLinkableHash,
+    #[serde(default)]
+    pub link_type: Option<LT>,
+    #[serde(default)]
+    pub tag_prefix: Option<LinkTag>,
+    #[serde(default, with = "crate::hash_serde::agent_pubkey::option")]
+    pub author: Option<AgentPubKey>,
+    /// Inclusive lower bound
+    #[serde(default)]
+    pub after: Option<Timestamp>,
+    /// Exclusive upper bound
+    #[serde(default)]
+    pub before: Option<Timestamp>,
+}
+
+impl<LT> LinkQueryInput<LT> {
+    /// Create a query with no filters
+    pub fn new(base: AnyLinkableHash) -> Self {
+        LinkQueryInput {
+            base,
+            link_type: None,
+            tag_prefix: None,
+            author: None,
+            after: None,
+            before: None,
+        }
+    }
+
+    /// Check that the filters are coherent
+    pub fn check(&self) -> ExternResult<()> {
+        if let (Some(after), Some(before)) = (&self.after, &self.before) {
+            if after >= before {
+                Err(guest_error!(
+                    format!("Link query time range is empty: {} >= {}", after.as_micros(), before.as_micros() )
+                ))?
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Expect the base to be one of the given kinds
+    pub fn require_base_kind(&self, kinds: &[HashKind]) -> ExternResult<HashKind> {
+        require_linkable_kind( "base", &self.base, kinds )
+    }
+
+    /// Check a tag against the tag prefix filter
+    pub fn matches_tag(&self, tag: &LinkTag) -> bool {
+        match &self.tag_prefix {
+            Some(prefix) => tag.0.starts_with( &prefix.0 ),
+            None => true,
+        }
+    }
+
+    /// Check an author against the author filter
+    pub fn matches_author(&self, author: &AgentPubKey) -> bool {
+        match &self.author {
+            Some(expected) => expected == author,
+            None => true,
+        }
+    }
+
+    /// Check a timestamp against the time range filter
+    pub fn matches_timestamp(&self, timestamp: &Timestamp) -> bool {
+        let after_start = match &self.after {
+            Some(after) => timestamp >= after,
+            None => true,
+        };
+        let before_end = match &self.before {
+            Some(before) => timestamp < before,
+            None => true,
+        };
+
+        after_start && before_end
+    }
+
+    /// Check a link's tag, author and timestamp against every filter except the link type
+    pub fn matches(&self, tag: &LinkTag, author: &AgentPubKey, timestamp: &Timestamp) -> bool {
+        self.matches_tag( tag )
+            && self.matches_author( author )
+            && self.matches_timestamp( timestamp )
+    }
+}
+
+/// Input for operating on one specific link
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct LinkTargetInput<LT> {
+    #[serde(with = "crate::hash_serde::any_linkable_hash")]
+    pub base: AnyLinkableHash,
+    #[serde(with = "crate::hash_serde::any_linkable_hash")]
+    pub target: AnyLinkableHash,
+    pub link_type: LT,
+    #[serde(default)]
+    pub tag: Option<LinkTag>,
+}
+
+impl<LT> LinkTargetInput<LT> {
+    /// Expect the base to be one of the given kinds
+    pub fn require_base_kind(&self, kinds: &[HashKind]) -> ExternResult<HashKind> {
+        require_linkable_kind( "base", &self.base, kinds )
+    }
+
+    /// Expect the target to be one of the given kinds
+    pub fn require_target_kind(&self, kinds: &[HashKind]) -> ExternResult<HashKind> {
+        require_linkable_kind( "target", &self.target, kinds )
+    }
+}
@@ -1,7 +1,51 @@
 mod macros;
+mod entity;
+mod fork;
+mod hash_kind;
+mod inputs;
+mod link_tag;
+mod link_rule;
+mod link_auth;
+mod summoned;
+mod validation;
+mod properties;
+mod roles;
+mod activity;
+mod rate_limit;
+mod limits;
+mod versioned;
+mod migration;
+mod membrane;
+mod progenitor;
+mod signed;
+mod countersigning;
+mod entry_kind;
+
+pub mod hash_serde;
 
 pub use hdi;
 pub use holo_hash;
+pub use entity::*;
+pub use fork::*;
+pub use hash_kind::*;
+pub use inputs::*;
+pub use link_tag::*;
+pub use link_rule::*;
+pub use link_auth::*;
+pub use summoned::*;
+pub use validation::*;
+pub use properties::*;
+pub use roles::*;
+pub use activity::*;
+pub use rate_limit::*;
+pub use limits::*;
+pub use versioned::*;
+pub use migration::*;
+pub use membrane::*;
+pub use progenitor::*;
+pub use signed::*;
+pub use countersigning::*;
+pub use entry_kind::*;
 
 use core::convert::{ TryFrom, TryInto };
 use hdi::prelude::{
@@ -9,8 +53,9 @@ use hdi::prelude::{
     must_get_entry,
     must_get_valid_record,
     ExternResult, WasmError, WasmErrorInner,
-    Deserialize, Serialize, SerializedBytesError,
-    ActionHash, EntryHash, ExternalHash, AnyDhtHash, AnyLinkableHash,
+    SerializedBytesError,
+    ActionHash, EntryHash, ExternalHash, AgentPubKey, DnaHash,
+    AnyDhtHash, AnyLinkableHash,
     Record, Action, Entry, EntryCreationAction, ActionType,
     SignedActionHashed, EntryHashed,
     AppEntryDef, ScopedEntryDefIndex,
@@ -213,27 +258,18 @@ pub trait AnyLinkableHashTransformer : Sized {
     fn must_be_action_hash(&self) -> ExternResult<ActionHash>;
     /// Expect hash type to be an [`EntryHash`] or error
     fn must_be_entry_hash(&self) -> ExternResult<EntryHash>;
+    /// Expect hash type to be an [`AgentPubKey`] or error
+    ///
+    /// Agent keys are linkable as entry hashes, so any entry hash will be reinterpreted as an
+    /// [`AgentPubKey`].
+    fn must_be_agent_pubkey(&self) -> ExternResult<AgentPubKey>;
+    /// Expect hash type to be an [`ExternalHash`] or error
+    fn must_be_external_hash(&self) -> ExternResult<ExternalHash>;
 }
 
 impl AnyLinkableHashTransformer for AnyLinkableHash {
     fn try_from_string(input: &str) -> ExternResult<Self> {
-        let action_result = ActionHash::try_from( input.to_string() );
-        let entry_result = EntryHash::try_from( input.to_string() );
-        let external_result = ExternalHash::try_from( input.to_string() );
-
-        Ok(
-            match (action_result.is_ok(), entry_result.is_ok(), external_result.is_ok()) {
-                (true, false, false) => action_result.unwrap().into(),
-                (false, true, false) => entry_result.unwrap().into(),
-                (false, false, true) => external_result.unwrap().into(),
-                (false, false, false) => Err(guest_error!(
-                    format!("String '{}' must be an Action or Entry hash", input )
-                ))?,
-                _ => Err(guest_error!(
-                    format!("String '{}' matched multiple hash types; this should not be possible", input )
-                ))?,
-            }
-        )
+        decode_hash_string( input )
     }
 
     fn must_be_action_hash(&self) -> ExternResult<ActionHash> {
@@ -253,31 +289,95 @@ impl AnyLinkableHashTransformer for AnyLinkableHash {
             ))?,
         }
     }
+
+    fn must_be_agent_pubkey(&self) -> ExternResult<AgentPubKey> {
+        match self.to_owned().into_entry_hash() {
+            Some(hash) => Ok( AgentPubKey::from_raw_36( hash.get_raw_36().to_vec() ) ),
+            None => Err(guest_error!(
+                format!("Any-linkable hash must be an agent pubkey; not '{}'", self )
+            ))?,
+        }
+    }
+
+    fn must_be_external_hash(&self) -> ExternResult<ExternalHash> {
+        match self.to_owned().into_primitive() {
+            AnyLinkableHashPrimitive::External(hash) => Ok( hash ),
+            _ => Err(guest_error!(
+                format!("Any-linkable hash must be an external hash; not '{}'", self )
+            ))?,
+        }
+    }
 }
 
 /// Extend [`AnyDhtHash`] transformations
 pub trait AnyDhtHashTransformer : Sized {
     /// Automatically determine correct type from a string
     fn try_from_string(input: &str) -> ExternResult<Self>;
+    /// Expect hash type to be an [`ActionHash`] or error
+    fn must_be_action_hash(&self) -> ExternResult<ActionHash>;
+    /// Expect hash type to be an [`EntryHash`] or error
+    fn must_be_entry_hash(&self) -> ExternResult<EntryHash>;
+    /// Expect hash type to be an [`AgentPubKey`] or error
+    ///
+    /// Agent keys are DHT addressable as entry hashes, so any entry hash will be reinterpreted as
+    /// an [`AgentPubKey`].
+    fn must_be_agent_pubkey(&self) -> ExternResult<AgentPubKey>;
 }
 
 impl AnyDhtHashTransformer for AnyDhtHash {
     fn try_from_string(input: &str) -> ExternResult<Self> {
-        let action_result = ActionHash::try_from( input.to_string() );
-        let entry_result = EntryHash::try_from( input.to_string() );
-
-        Ok(
-            match (action_result.is_ok(), entry_result.is_ok()) {
-                (true, false) => action_result.unwrap().into(),
-                (false, true) => entry_result.unwrap().into(),
-                (false, false) => Err(guest_error!(
-                    format!("String '{}' must be an Action or Entry hash", input )
-                ))?,
-                (true, true) => Err(guest_error!(
-                    format!("String '{}' matched Action and Entry hash; this should not be possible", input )
-                ))?,
-            }
-        )
+        decode_hash_string( input )
+    }
+
+    fn must_be_action_hash(&self) -> ExternResult<ActionHash> {
+        match self.to_owned().into_action_hash() {
+            Some(hash) => Ok( hash ),
+            None => Err(guest_error!(
+                format!("Any-DHT hash must be an action hash; not '{}'", self )
+            ))?,
+        }
+    }
+
+    fn must_be_entry_hash(&self) -> ExternResult<EntryHash> {
+        match self.to_owned().into_entry_hash() {
+            Some(hash) => Ok( hash ),
+            None => Err(guest_error!(
+                format!("Any-DHT hash must be an entry hash; not '{}'", self )
+            ))?,
+        }
+    }
+
+    fn must_be_agent_pubkey(&self) -> ExternResult<AgentPubKey> {
+        match self.to_owned().into_entry_hash() {
+            Some(hash) => Ok( AgentPubKey::from_raw_36( hash.get_raw_36().to_vec() ) ),
+            None => Err(guest_error!(
+                format!("Any-DHT hash must be an agent pubkey; not '{}'", self )
+            ))?,
+        }
+    }
+}
+
+/// Extend [`AgentPubKey`] transformations
+pub trait AgentPubKeyTransformer : Sized {
+    /// Decode a string that must be an agent pubkey
+    fn try_from_string(input: &str) -> ExternResult<Self>;
+}
+
+impl AgentPubKeyTransformer for AgentPubKey {
+    fn try_from_string(input: &str) -> ExternResult<Self> {
+        decode_hash_string( input )
+    }
+}
+
+/// Extend [`DnaHash`] transformations
+pub trait DnaHashTransformer : Sized {
+    /// Decode a string that must be a DNA hash
+    fn try_from_string(input: &str) -> ExternResult<Self>;
+}
+
+impl DnaHashTransformer for DnaHash {
+    fn try_from_string(input: &str) -> ExternResult<Self> {
+        decode_hash_string( input )
     }
 }
 
@@ -491,13 +591,3 @@ where
     })
 }
 
-
-//
-// Standard Inputs
-//
-/// Input for getting links based on direction (ignoring type/tag)
-#[derive(Clone, Serialize, Deserialize, Debug)]
-pub struct LinkDirectionInput {
-    pub base: AnyLinkableHash,
-    pub target: AnyLinkableHash,
-}
@@ -0,0 +1,272 @@
+use std::collections::{ BTreeMap, BTreeSet, HashMap, HashSet };
+use hdi::prelude::{
+    ExternResult,
+    Entry,
+    ValidateCallbackResult,
+};
+use crate::guest_error;
+
+
+//
+// Generic Limits
+//
+/// Structural limits for any serialized entry
+///
+/// `None` means unlimited.
+#[derive(Clone, Debug, Default)]
+pub struct Limits {
+    /// Maximum size of the serialized entry
+    pub max_bytes: Option<usize>,
+    /// Maximum characters in any string value (map keys, such as field names, are not checked)
+    pub max_string_len: Option<usize>,
+    /// Maximum items in any array or map
+    pub max_collection_len: Option<usize>,
+}
+
+fn check_value_limits(value: &rmpv::Value, path: String, limits: &Limits) -> Option<String> {
+    match value {
+        rmpv::Value::String(string) => {
+            let len = string.as_str()
+                .map(|s| s.chars().count() )
+                .unwrap_or( string.as_bytes().len() );
+
+            match limits.max_string_len {
+                Some(max) if len > max => Some(
+                    format!("String at '{}' has {} characters; the limit is {}", path, len, max )
+                ),
+                _ => None,
+            }
+        },
+        rmpv::Value::Array(items) => {
+            if let Some(max) = limits.max_collection_len {
+                if items.len() > max {
+                    return Some(
+                        format!("Array at '{}' has {} items; the limit is {}", path, items.len(), max )
+                    );
+                }
+            }
+
+            items.iter().enumerate()
+                .find_map(|(i, item)| check_value_limits( item, format!("{}[{}]", path, i ), limits ) )
+        },
+        rmpv::Value::Map(entries) => {
+            if let Some(max) = limits.max_collection_len {
+                if entries.len() > max {
+                    return Some(
+                        format!("Map at '{}' has {} items; the limit is {}", path, entries.len(), max )
+                    );
+                }
+            }
+
+            entries.iter()
+                .find_map(|(key, value)| {
+                    let key_path = match key.as_str() {
+                        Some(name) => format!("{}.{}", path, name ),
+                        None => format!("{}[{}]", path, key ),
+                    };
+
+                    check_value_limits( value, key_path, limits )
+                })
+        },
+        _ => None,
+    }
+}
+
+/// Check an [`Entry`] against generic [`Limits`]
+///
+/// Only app (and countersigned app) entries are checked; other entries are always valid.
+///
+/// ##### Example: Basic Usage
+/// ```
+/// # use hdi::prelude::*;
+/// # use hdi_extensions::*;
+///
+/// fn validate_entry(entry: Entry) -> ExternResult<ValidateCallbackResult> {
+///     check_entry_limits( &entry, &Limits {
+///         max_bytes: Some( 10_000 ),
+///         max_string_len: Some( 1_000 ),
+///         max_collection_len: Some( 100 ),
+///     })
+/// }
+/// ```
+pub fn check_entry_limits(entry: &Entry, limits: &Limits) -> ExternResult<ValidateCallbackResult> {
+    let bytes = match entry {
+        Entry::App(app_bytes) => app_bytes.0.bytes(),
+        Entry::CounterSign(_, app_bytes) => app_bytes.0.bytes(),
+        _ => return Ok(ValidateCallbackResult::Valid),
+    };
+
+    if let Some(max) = limits.max_bytes {
+        if bytes.len() > max {
+            return Ok(ValidateCallbackResult::Invalid(
+                format!("Entry is {} bytes; the limit is {}", bytes.len(), max )
+            ));
+        }
+    }
+
+    let value = rmpv::decode::read_value( &mut bytes.as_slice() )
+        .map_err(|err| guest_error!(
+            "Could not decode entry for limit check: {}", err
+        ))?;
+
+    Ok(
+        match check_value_limits( &value, "$".to_string(), limits ) {
+            Some(msg) => ValidateCallbackResult::Invalid( msg ),
+            None => ValidateCallbackResult::Valid,
+        }
+    )
+}
+
+
+//
+// Field Limits
+//
+pub use whi_hdi_extensions_derive::EntryLimits;
+
+/// Length of a field as seen by `max_len` and `min_len`
+pub trait LimitLength {
+    /// Characters for strings, items for collections
+    fn limit_len(&self) -> usize;
+}
+
+/// Size of a field as seen by `max_bytes`
+///
+/// Only implemented for strings and raw bytes; collections are limited with `max_len`.
+pub trait LimitBytes {
+    /// UTF-8 bytes for strings, raw bytes for byte vectors
+    fn limit_bytes(&self) -> usize;
+}
+
+impl LimitLength for String {
+    fn limit_len(&self) -> usize {
+        self.chars().count()
+    }
+}
+
+impl LimitBytes for String {
+    fn limit_bytes(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> LimitLength for Vec<T> {
+    fn limit_len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl LimitBytes for Vec<u8> {
+    fn limit_bytes(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<K, V> LimitLength for BTreeMap<K, V> {
+    fn limit_len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> LimitLength for BTreeSet<T> {
+    fn limit_len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<K, V, S> LimitLength for HashMap<K, V, S> {
+    fn limit_len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T, S> LimitLength for HashSet<T, S> {
+    fn limit_len(&self) -> usize {
+        self.len()
+    }
+}
+
+/// `None` has a length of 0
+impl<T> LimitLength for Option<T>
+where
+    T: LimitLength,
+{
+    fn limit_len(&self) -> usize {
+        self.as_ref().map(|value| value.limit_len() ).unwrap_or(0)
+    }
+}
+
+/// `None` has a size of 0
+impl<T> LimitBytes for Option<T>
+where
+    T: LimitBytes,
+{
+    fn limit_bytes(&self) -> usize {
+        self.as_ref().map(|value| value.limit_bytes() ).unwrap_or(0)
+    }
+}
+
+/// A limit on a single field (see [`EntryLimits`])
+#[derive(Clone, Debug, PartialEq)]
+pub enum FieldLimit {
+    /// At most this many characters or items ([`LimitLength`])
+    MaxLen(usize),
+    /// At least this many characters or items ([`LimitLength`])
+    MinLen(usize),
+    /// At most this many bytes ([`LimitBytes`])
+    MaxBytes(usize),
+}
+
+impl FieldLimit {
+    /// Check a field's measured length or size, returning a message naming the field and limit
+    /// when violated
+    pub fn check(&self, field: &str, measured: usize) -> Option<String> {
+        match self {
+            FieldLimit::MaxLen(max) if measured > *max => Some(
+                format!("Field '{}' violates max_len: {} > {}", field, measured, max )
+            ),
+            FieldLimit::MinLen(min) if measured < *min => Some(
+                format!("Field '{}' violates min_len: {} < {}", field, measured, min )
+            ),
+            FieldLimit::MaxBytes(max) if measured > *max => Some(
+                format!("Field '{}' violates max_bytes: {} > {}", field, measured, max )
+            ),
+            _ => None,
+        }
+    }
+}
+
+/// Per-field limits for an entry struct
+///
+/// Usually derived with `#[limit(...)]` attributes on each field.  Available limits are `max_len`
+/// and `min_len` (see [`LimitLength`]) and `max_bytes` (see [`LimitBytes`]).  Fields are checked
+/// in declaration order and the first violation is reported.
+///
+/// ##### Example: Basic Usage
+/// ```
+/// # use hdi::prelude::*;
+/// # use hdi_extensions::*;
+///
+/// #[derive(EntryLimits)]
+/// struct PostEntry {
+///     #[limit(min_len = 1, max_len = 280, max_bytes = 1_120)]
+///     pub message: String,
+///     #[limit(max_len = 10)]
+///     pub tags: Vec<String>,
+/// }
+///
+/// fn validate_post(post: PostEntry) -> ExternResult<ValidateCallbackResult> {
+///     Ok( post.check_limits() )
+/// }
+/// ```
+pub trait EntryLimits {
+    /// Get a message for the first field that violates its limit
+    fn limit_violation(&self) -> Option<String>;
+
+    /// Check every field limit
+    fn check_limits(&self) -> ValidateCallbackResult {
+        match self.limit_violation() {
+            Some(msg) => ValidateCallbackResult::Invalid( msg ),
+            None => ValidateCallbackResult::Valid,
+        }
+    }
+}
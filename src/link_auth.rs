@@ -0,0 +1,92 @@
+use hdi::prelude::{
+    ExternResult,
+    AnyLinkableHash, AgentPubKey,
+    DeleteLink,
+    ValidateCallbackResult,
+};
+use holo_hash::AnyLinkableHashPrimitive;
+use crate::{
+    summon_action,
+    summon_create_link_action,
+};
+
+
+//
+// Ownership Checks
+//
+/// Check if an agent is the owner of a link base
+///
+/// An agent owns a base when the base is their agent key or an action they authored.  External
+/// bases have no owner.
+pub fn is_base_owner(base: &AnyLinkableHash, agent: &AgentPubKey) -> ExternResult<bool> {
+    Ok(
+        match base.to_owned().into_primitive() {
+            AnyLinkableHashPrimitive::Entry(entry_hash) => entry_hash.get_raw_36() == agent.get_raw_36(),
+            AnyLinkableHashPrimitive::Action(action_hash) => summon_action( &action_hash )?
+                .hashed.content.author() == agent,
+            AnyLinkableHashPrimitive::External(_) => false,
+        }
+    )
+}
+
+/// Check if the deleter of a link is the agent that created it
+pub fn is_link_creator(delete_link: &DeleteLink) -> ExternResult<bool> {
+    let create_link = summon_create_link_action( &delete_link.link_add_address )?;
+
+    Ok( create_link.author == delete_link.author )
+}
+
+/// Check if the deleter of a link is an owner of the link's base (see [`is_base_owner`])
+pub fn is_link_base_owner(delete_link: &DeleteLink) -> ExternResult<bool> {
+    is_base_owner( &delete_link.base_address, &delete_link.author )
+}
+
+
+//
+// Validation
+//
+/// Require the deleter of a link to be the agent that created it
+///
+/// ##### Example: Basic Usage
+/// ```
+/// # use hdi::prelude::*;
+/// # use hdi_extensions::*;
+///
+/// fn validate_delete_link(delete_link: DeleteLink) -> ExternResult<ValidateCallbackResult> {
+///     require_link_creator( &delete_link )
+/// }
+/// ```
+pub fn require_link_creator(delete_link: &DeleteLink) -> ExternResult<ValidateCallbackResult> {
+    Ok(
+        match is_link_creator( delete_link )? {
+            true => ValidateCallbackResult::Valid,
+            false => ValidateCallbackResult::Invalid(
+                format!("Link can only be deleted by its creator; not '{}'", delete_link.author )
+            ),
+        }
+    )
+}
+
+/// Require the deleter of a link to be an owner of the link's base (see [`is_base_owner`])
+pub fn require_base_owner(delete_link: &DeleteLink) -> ExternResult<ValidateCallbackResult> {
+    Ok(
+        match is_link_base_owner( delete_link )? {
+            true => ValidateCallbackResult::Valid,
+            false => ValidateCallbackResult::Invalid(
+                format!("Link can only be deleted by an owner of its base ({}); not '{}'", delete_link.base_address, delete_link.author )
+            ),
+        }
+    )
+}
+
+/// Require the deleter of a link to be its creator or an owner of its base
+pub fn require_link_creator_or_base_owner(delete_link: &DeleteLink) -> ExternResult<ValidateCallbackResult> {
+    Ok(
+        match is_link_creator( delete_link )? || is_link_base_owner( delete_link )? {
+            true => ValidateCallbackResult::Valid,
+            false => ValidateCallbackResult::Invalid(
+                format!("Link can only be deleted by its creator or an owner of its base ({}); not '{}'", delete_link.base_address, delete_link.author )
+            ),
+        }
+    )
+}
@@ -0,0 +1,181 @@
+use core::convert::TryFrom;
+use hdi::prelude::{
+    ExternResult, WasmError, WasmErrorInner,
+    AnyLinkableHash, AgentPubKey,
+    Record, Entry, CreateLink,
+    AppEntryDef, ScopedEntryDefIndex,
+    ValidateCallbackResult,
+};
+use crate::{
+    guest_error,
+    summon_creation_action,
+    detect_app_entry_def,
+    verify_app_entry_struct,
+    require_linkable_kind,
+    AnyLinkableHashTransformer,
+    HashKind,
+    ScopedTypeConnector,
+};
+
+
+/// Turn a guest error into an invalid message while passing through host errors
+///
+/// Host errors (eg. a `must_get_*` short-circuit) must propagate so that the conductor can report
+/// unresolved dependencies.
+pub(crate) fn guest_error_as_invalid(result: ExternResult<()>) -> ExternResult<Option<String>> {
+    match result {
+        Ok(()) => Ok(None),
+        Err(WasmError { error: WasmErrorInner::Guest(msg), .. }) => Ok(Some(msg)),
+        Err(error) => Err(error),
+    }
+}
+
+
+//
+// Link Rule
+//
+struct TargetEntryRule {
+    app_entry_def: AppEntryDef,
+    verify_struct: fn(&AnyLinkableHash) -> ExternResult<()>,
+}
+
+/// Declarative constraints for a link type
+///
+/// ##### Example: Basic Usage
+/// ```ignore
+/// use hdi::prelude::*;
+/// use hdi_extensions::*;
+///
+/// fn link_rule(link_type: &LinkTypes) -> LinkRule {
+///     match link_type {
+///         LinkTypes::AgentToPost => LinkRule::new()
+///             .base( &[ HashKind::Agent ] )
+///             .target( &[ HashKind::Action ] )
+///             .target_entry::<PostEntry,_,_>()
+///             .author_is_base(),
+///     }
+/// }
+///
+/// fn validate_create_link(link_type: LinkTypes, create_link: CreateLink) -> ExternResult<ValidateCallbackResult> {
+///     link_rule( &link_type ).validate_create_link( &create_link )
+/// }
+/// ```
+#[derive(Default)]
+pub struct LinkRule {
+    base_kinds: Option<Vec<HashKind>>,
+    target_kinds: Option<Vec<HashKind>>,
+    target_entry: Option<TargetEntryRule>,
+    author_is_base: bool,
+}
+
+impl LinkRule {
+    /// Create a rule with no constraints
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Require the base to be one of the given kinds
+    ///
+    /// An Agent-only rule summons entry hash bases and requires an agent key entry (see
+    /// [`require_linkable_kind`]).
+    pub fn base(mut self, kinds: &[HashKind]) -> Self {
+        self.base_kinds = Some( kinds.to_vec() );
+        self
+    }
+
+    /// Require the target to be one of the given kinds
+    ///
+    /// Agent-only targets are checked the same way as [`LinkRule::base`].
+    pub fn target(mut self, kinds: &[HashKind]) -> Self {
+        self.target_kinds = Some( kinds.to_vec() );
+        self
+    }
+
+    /// Require the target to be an app entry of type `T`
+    ///
+    /// Action targets must be creation actions with `T`'s [`AppEntryDef`]; entry targets must
+    /// deserialize into `T`.
+    pub fn target_entry<T,ET,ETU>(mut self) -> Self
+    where
+        T: ScopedTypeConnector<ET,ETU>
+            + TryFrom<Record, Error = WasmError>
+            + TryFrom<Entry, Error = WasmError>,
+        ScopedEntryDefIndex: for<'a> TryFrom<&'a ET, Error = WasmError>,
+    {
+        self.target_entry = Some(TargetEntryRule {
+            app_entry_def: T::app_entry_def(),
+            verify_struct: verify_app_entry_struct::<T>,
+        });
+        self
+    }
+
+    /// Require the link author to be the base (which must be an agent)
+    pub fn author_is_base(mut self) -> Self {
+        self.author_is_base = true;
+        self
+    }
+
+    fn check(
+        &self,
+        base: &AnyLinkableHash,
+        target: &AnyLinkableHash,
+        author: &AgentPubKey,
+    ) -> ExternResult<()> {
+        if let Some(kinds) = &self.base_kinds {
+            require_linkable_kind( "base", base, kinds )?;
+        }
+
+        if let Some(kinds) = &self.target_kinds {
+            require_linkable_kind( "target", target, kinds )?;
+        }
+
+        if self.author_is_base {
+            let base_agent = base.must_be_agent_pubkey()?;
+
+            if base_agent != *author {
+                Err(guest_error!(
+                    format!("Link author ({}) must be the base agent ({})", author, base_agent )
+                ))?
+            }
+        }
+
+        if let Some(rule) = &self.target_entry {
+            if let Some(action_hash) = target.to_owned().into_action_hash() {
+                let entry_def = detect_app_entry_def( &summon_creation_action( &action_hash )? )?;
+
+                if entry_def != rule.app_entry_def {
+                    Err(guest_error!(
+                        format!("Link target entry def mismatch: {:?} != {:?}", entry_def, rule.app_entry_def )
+                    ))?
+                }
+            }
+
+            (rule.verify_struct)( target )?;
+        }
+
+        Ok(())
+    }
+
+    /// Evaluate every constraint
+    ///
+    /// Broken constraints return [`ValidateCallbackResult::Invalid`]; host errors (eg. missing
+    /// dependencies) are returned as errors.
+    pub fn validate(
+        &self,
+        base: &AnyLinkableHash,
+        target: &AnyLinkableHash,
+        author: &AgentPubKey,
+    ) -> ExternResult<ValidateCallbackResult> {
+        Ok(
+            match guest_error_as_invalid( self.check( base, target, author ) )? {
+                Some(msg) => ValidateCallbackResult::Invalid( msg ),
+                None => ValidateCallbackResult::Valid,
+            }
+        )
+    }
+
+    /// Evaluate every constraint against a [`CreateLink`] action
+    pub fn validate_create_link(&self, create_link: &CreateLink) -> ExternResult<ValidateCallbackResult> {
+        self.validate( &create_link.base_address, &create_link.target_address, &create_link.author )
+    }
+}
@@ -0,0 +1,153 @@
+use core::convert::TryFrom;
+use hdi::prelude::{
+    ExternResult, WasmError,
+    Serialize,
+    ActionHash, LinkTag,
+    ValidateCallbackResult,
+};
+use serde::de::DeserializeOwned;
+use crate::{
+    guest_error,
+    summon_create_link_action,
+};
+
+
+/// Maximum number of bytes the conductor accepts in a link tag
+pub const MAX_LINK_TAG_SIZE : usize = 1000;
+
+
+//
+// Typed Link Tag
+//
+/// A link tag carrying a msgpack encoded struct
+///
+/// ##### Example: Basic Usage
+/// ```
+/// # use hdi::prelude::*;
+/// # use hdi_extensions::*;
+///
+/// #[derive(Clone, Serialize, Deserialize, Debug)]
+/// struct PostTag {
+///     pub published_at: u64,
+/// }
+///
+/// fn test(tag: LinkTag) -> ExternResult<()> {
+///     let typed : TypedLinkTag<PostTag> = TypedLinkTag::decode( &tag )?;
+///     let tag : LinkTag = typed.encode()?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct TypedLinkTag<T>(pub T);
+
+impl<T> TypedLinkTag<T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    /// Wrap a tag struct
+    pub fn new(content: T) -> Self {
+        TypedLinkTag( content )
+    }
+
+    /// Get the tag struct
+    pub fn content(&self) -> &T {
+        &self.0
+    }
+
+    /// Unwrap the tag struct
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+
+    /// Encode into a [`LinkTag`], checking it against [`MAX_LINK_TAG_SIZE`]
+    pub fn encode(&self) -> ExternResult<LinkTag> {
+        let bytes = rmp_serde::to_vec_named( &self.0 )
+            .map_err(|err| guest_error!(
+                format!("Could not encode link tag: {}", err )
+            ))?;
+
+        if bytes.len() > MAX_LINK_TAG_SIZE {
+            Err(guest_error!(
+                format!("Encoded link tag is {} bytes; the limit is {}", bytes.len(), MAX_LINK_TAG_SIZE )
+            ))?
+        }
+
+        Ok( LinkTag::new( bytes ) )
+    }
+
+    /// Decode from a [`LinkTag`]
+    pub fn decode(tag: &LinkTag) -> ExternResult<Self> {
+        Ok( TypedLinkTag(
+            rmp_serde::from_slice( &tag.0 )
+                .map_err(|err| guest_error!(
+                    format!("Link tag does not match the expected schema: {}", err )
+                ))?
+        ) )
+    }
+}
+
+impl<T> TryFrom<&LinkTag> for TypedLinkTag<T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    type Error = WasmError;
+
+    fn try_from(tag: &LinkTag) -> Result<Self, Self::Error> {
+        Self::decode( tag )
+    }
+}
+
+impl<T> TryFrom<TypedLinkTag<T>> for LinkTag
+where
+    T: Serialize + DeserializeOwned,
+{
+    type Error = WasmError;
+
+    fn try_from(tag: TypedLinkTag<T>) -> Result<Self, Self::Error> {
+        tag.encode()
+    }
+}
+
+
+//
+// Validation
+//
+/// Check that a [`LinkTag`] fits the size limit and decodes to the expected struct
+///
+/// Returns [`ValidateCallbackResult::Invalid`] instead of an error when the tag does not match.
+pub fn validate_link_tag<T>(tag: &LinkTag) -> ExternResult<ValidateCallbackResult>
+where
+    T: Serialize + DeserializeOwned,
+{
+    if tag.0.len() > MAX_LINK_TAG_SIZE {
+        return Ok(ValidateCallbackResult::Invalid(
+            format!("Link tag is {} bytes; the limit is {}", tag.0.len(), MAX_LINK_TAG_SIZE )
+        ));
+    }
+
+    Ok(
+        match rmp_serde::from_slice::<T>( &tag.0 ) {
+            Ok(_) => ValidateCallbackResult::Valid,
+            Err(err) => ValidateCallbackResult::Invalid(
+                format!("Link tag does not match the expected schema: {}", err )
+            ),
+        }
+    )
+}
+
+/// Get the typed tag of a [`CreateLink`](hdi::prelude::CreateLink) action
+pub fn summon_link_tag<T>(create_link_addr: &ActionHash) -> ExternResult<TypedLinkTag<T>>
+where
+    T: Serialize + DeserializeOwned,
+{
+    TypedLinkTag::decode( &summon_create_link_action( create_link_addr )?.tag )
+}
+
+/// Validate the tag of a [`CreateLink`](hdi::prelude::CreateLink) action (see
+/// [`validate_link_tag`])
+pub fn validate_create_link_tag<T>(create_link_addr: &ActionHash) -> ExternResult<ValidateCallbackResult>
+where
+    T: Serialize + DeserializeOwned,
+{
+    validate_link_tag::<T>( &summon_create_link_action( create_link_addr )?.tag )
+}
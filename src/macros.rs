@@ -18,6 +18,8 @@ macro_rules! valid {
 
 /// Return an [`hdi::prelude::ValidateCallbackResult::Invalid`]
 ///
+/// Accepts a `String` or format arguments.
+///
 /// ##### Example: Basic Usage
 /// ```
 /// # use hdi::prelude::*;
@@ -27,23 +29,131 @@ macro_rules! valid {
 ///     invalid!(format!("Unauthorized"))
 /// }
 /// ```
+///
+/// ##### Example: Format Arguments
+/// ```
+/// # use hdi::prelude::*;
+/// # use hdi_extensions::*;
+///
+/// fn fail(author: AgentPubKey) -> ExternResult<ValidateCallbackResult> {
+///     invalid!("Unauthorized author: {}", author )
+/// }
+/// ```
 #[macro_export]
 macro_rules! invalid {
+    ( $format:literal $(,)? ) => {
+        return Ok($crate::hdi::prelude::ValidateCallbackResult::Invalid(format!( $format )))
+    };
+    ( $format:literal, $($args:tt)+ ) => {
+        return Ok($crate::hdi::prelude::ValidateCallbackResult::Invalid(format!( $format, $($args)+ )))
+    };
     ( $message:expr ) => {
         return Ok($crate::hdi::prelude::ValidateCallbackResult::Invalid($message))
     };
 }
 
+/// Return an [`hdi::prelude::ValidateCallbackResult::Invalid`] unless the condition is true
+///
+/// ##### Example: Basic Usage
+/// ```
+/// # use hdi::prelude::*;
+/// # use hdi_extensions::*;
+///
+/// fn check(message: String) -> ExternResult<ValidateCallbackResult> {
+///     ensure_valid!( message.len() <= 280, "Message is {} characters; the limit is 280", message.len() );
+///     valid!()
+/// }
+/// ```
+#[macro_export]
+macro_rules! ensure_valid {
+    ( $condition:expr, $($message:tt)+ ) => {
+        if !( $condition ) {
+            $crate::invalid!( $($message)+ );
+        }
+    };
+}
+
+/// Unwrap a `Result` or return its error as an [`hdi::prelude::ValidateCallbackResult::Invalid`]
+///
+/// The error must implement `Display`.  Use this instead of `?` when the error means the data is
+/// invalid, because `?` fails validation with an error rather than an `Invalid` result.
+///
+/// ##### Example: Basic Usage
+/// ```
+/// # use hdi::prelude::*;
+/// # use hdi_extensions::*;
+///
+/// fn check(input: String) -> ExternResult<ValidateCallbackResult> {
+///     let count : u32 = try_valid!( input.parse::<u32>(), "Invalid count" );
+///     valid!()
+/// }
+/// ```
+#[macro_export]
+macro_rules! try_valid {
+    ( $result:expr $(,)? ) => {
+        match $result {
+            Ok(value) => value,
+            Err(error) => $crate::invalid!("{}", error ),
+        }
+    };
+    ( $result:expr, $context:expr $(,)? ) => {
+        match $result {
+            Ok(value) => value,
+            Err(error) => $crate::invalid!("{}: {}", $context, error ),
+        }
+    };
+}
+
+/// Unwrap a [`Summoned::Found`](crate::Summoned::Found) or return the matching
+/// [`hdi::prelude::ValidateCallbackResult`]
+///
+/// - [`Summoned::Unresolved`](crate::Summoned::Unresolved) returns `UnresolvedDependencies`
+/// - [`Summoned::Invalid`](crate::Summoned::Invalid) returns `Invalid`
+///
+/// ##### Example: Basic Usage
+/// ```
+/// # use hdi::prelude::*;
+/// # use hdi_extensions::*;
+///
+/// fn check(addr: ActionHash) -> ExternResult<ValidateCallbackResult> {
+///     let record = validation_result!( try_summon_valid_record( &addr )? );
+///     valid!()
+/// }
+/// ```
+#[macro_export]
+macro_rules! validation_result {
+    ( $summoned:expr ) => {
+        match $summoned {
+            $crate::Summoned::Found(value) => value,
+            $crate::Summoned::Unresolved(deps) => return Ok(
+                $crate::hdi::prelude::ValidateCallbackResult::UnresolvedDependencies(deps)
+            ),
+            $crate::Summoned::Invalid(message) => return Ok(
+                $crate::hdi::prelude::ValidateCallbackResult::Invalid(message)
+            ),
+        }
+    };
+}
+
 /// Shortcut for `wasm_error!(WasmErrorInner::Guest( ... ))`
 ///
+/// Accepts a `String` or format arguments.
+///
 /// ##### Example: Basic Usage
 /// ```
 /// # use hdi_extensions::*;
 ///
 /// guest_error!(format!("Something's wrong"));
+/// guest_error!("Something's wrong with {}", "this" );
 /// ```
 #[macro_export]
 macro_rules! guest_error {
+    ( $format:literal $(,)? ) => {
+        $crate::guest_error!( format!( $format ) )
+    };
+    ( $format:literal, $($args:tt)+ ) => {
+        $crate::guest_error!( format!( $format, $($args)+ ) )
+    };
     ( $message:expr ) => {
         {
             use $crate::hdi::prelude::*;
@@ -0,0 +1,187 @@
+use hdi::prelude::{
+    ExternResult,
+    Deserialize, Serialize,
+    AgentPubKey, Signature,
+    AgentValidationPkg, MembraneProof,
+    ValidateCallbackResult,
+};
+use hdi::ed25519::verify_signature;
+use crate::dna_properties_or_default;
+use crate::properties::AgentKey;
+
+
+//
+// Verifiers
+//
+/// Decides whether an agent may join the network
+///
+/// Closures with the same signature as [`MembraneProofVerifier::verify`] can also be used.
+pub trait MembraneProofVerifier {
+    /// Verify the membrane proof that `agent` provided when joining
+    fn verify(
+        &self,
+        agent: &AgentPubKey,
+        membrane_proof: Option<&MembraneProof>,
+    ) -> ExternResult<ValidateCallbackResult>;
+}
+
+impl<F> MembraneProofVerifier for F
+where
+    F: Fn(&AgentPubKey, Option<&MembraneProof>) -> ExternResult<ValidateCallbackResult>,
+{
+    fn verify(
+        &self,
+        agent: &AgentPubKey,
+        membrane_proof: Option<&MembraneProof>,
+    ) -> ExternResult<ValidateCallbackResult> {
+        self( agent, membrane_proof )
+    }
+}
+
+/// Any agent may join
+#[derive(Clone, Debug, Default)]
+pub struct OpenMembership;
+
+impl MembraneProofVerifier for OpenMembership {
+    fn verify(
+        &self,
+        _agent: &AgentPubKey,
+        _membrane_proof: Option<&MembraneProof>,
+    ) -> ExternResult<ValidateCallbackResult> {
+        Ok(ValidateCallbackResult::Valid)
+    }
+}
+
+/// Only the listed agents may join (the membrane proof is ignored)
+#[derive(Clone, Debug, Default)]
+pub struct AllowListedAgents(pub Vec<AgentPubKey>);
+
+#[derive(Deserialize, Default)]
+struct AllowListProperties {
+    #[serde(default)]
+    allowed_agents: Vec<AgentKey>,
+}
+
+impl AllowListedAgents {
+    /// Read the allow-list from the DNA properties' `allowed_agents`
+    ///
+    /// ```yaml
+    /// properties:
+    ///   allowed_agents:
+    ///     - uhCAk...
+    /// ```
+    pub fn from_properties() -> ExternResult<Self> {
+        Ok(AllowListedAgents(
+            dna_properties_or_default::<AllowListProperties>()?
+                .allowed_agents
+                .into_iter()
+                .map(|key| key.0 )
+                .collect()
+        ))
+    }
+}
+
+impl MembraneProofVerifier for AllowListedAgents {
+    fn verify(
+        &self,
+        agent: &AgentPubKey,
+        _membrane_proof: Option<&MembraneProof>,
+    ) -> ExternResult<ValidateCallbackResult> {
+        Ok(
+            match self.0.contains( agent ) {
+                true => ValidateCallbackResult::Valid,
+                false => ValidateCallbackResult::Invalid(
+                    format!("Agent ({}) is not in the membrane allow-list", agent )
+                ),
+            }
+        )
+    }
+}
+
+/// Membrane proof for [`ProgenitorInvite`]
+///
+/// `signature` is the progenitor's signature of `agent` (eg. `sign( progenitor, agent )`).
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct MembraneInvite {
+    pub agent: AgentPubKey,
+    pub signature: Signature,
+}
+
+/// Agents may join with an invite signed by the progenitor
+#[derive(Clone, Debug)]
+pub struct ProgenitorInvite {
+    pub progenitor: AgentPubKey,
+}
+
+impl ProgenitorInvite {
+    /// Accept invites signed by the given agent
+    pub fn new(progenitor: AgentPubKey) -> Self {
+        ProgenitorInvite { progenitor }
+    }
+}
+
+impl MembraneProofVerifier for ProgenitorInvite {
+    fn verify(
+        &self,
+        agent: &AgentPubKey,
+        membrane_proof: Option<&MembraneProof>,
+    ) -> ExternResult<ValidateCallbackResult> {
+        // The progenitor does not need an invite
+        if *agent == self.progenitor {
+            return Ok(ValidateCallbackResult::Valid);
+        }
+
+        let proof = match membrane_proof {
+            Some(proof) => proof,
+            None => return Ok(ValidateCallbackResult::Invalid(
+                "Membrane proof is required".to_string()
+            )),
+        };
+
+        let invite : MembraneInvite = match rmp_serde::from_slice( proof.bytes() ) {
+            Ok(invite) => invite,
+            Err(err) => return Ok(ValidateCallbackResult::Invalid(
+                format!("Membrane proof is not an invite: {}", err )
+            )),
+        };
+
+        if invite.agent != *agent {
+            return Ok(ValidateCallbackResult::Invalid(
+                format!("Invite is for agent '{}'; not '{}'", invite.agent, agent )
+            ));
+        }
+
+        if !verify_signature( self.progenitor.clone(), invite.signature, invite.agent )? {
+            return Ok(ValidateCallbackResult::Invalid(
+                format!("Invite was not signed by the progenitor ({})", self.progenitor )
+            ));
+        }
+
+        Ok(ValidateCallbackResult::Valid)
+    }
+}
+
+
+//
+// Validation
+//
+/// Validate an `OpRecord::AgentValidationPkg` using the given verifier
+///
+/// ##### Example: Basic Usage
+/// ```
+/// # use hdi::prelude::*;
+/// # use hdi_extensions::*;
+///
+/// fn validate_join(action: AgentValidationPkg) -> ExternResult<ValidateCallbackResult> {
+///     validate_membrane_proof( &action, &AllowListedAgents::from_properties()? )
+/// }
+/// ```
+pub fn validate_membrane_proof<V>(
+    action: &AgentValidationPkg,
+    verifier: &V,
+) -> ExternResult<ValidateCallbackResult>
+where
+    V: MembraneProofVerifier + ?Sized,
+{
+    verifier.verify( &action.author, action.membrane_proof.as_ref() )
+}
@@ -0,0 +1,185 @@
+use hdi::prelude::{
+    ExternResult,
+    Deserialize,
+    ActionHash, AgentPubKey, DnaHash,
+    Action, ActionType, OpenChain, CloseChain, CreateLink,
+    MigrationTarget,
+    ValidateCallbackResult,
+};
+use crate::{
+    dna_properties_or_default,
+    find_latest_action_of_type,
+    summon_action,
+};
+
+
+//
+// Properties
+//
+#[derive(Deserialize)]
+struct DnaKey(
+    #[serde(with = "crate::hash_serde::dna_hash")]
+    DnaHash
+);
+
+/// DNA properties following the `previous_dnas` convention
+///
+/// Lists the DNAs that agents may migrate from.
+///
+/// ```yaml
+/// properties:
+///   previous_dnas:
+///     - uhC0k...
+/// ```
+#[derive(Deserialize, Debug, Default)]
+pub struct MigrationProperties {
+    #[serde(default, deserialize_with = "deserialize_dna_hashes")]
+    pub previous_dnas: Vec<DnaHash>,
+}
+
+fn deserialize_dna_hashes<'de, D>(deserializer: D) -> Result<Vec<DnaHash>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Ok(
+        Vec::<DnaKey>::deserialize( deserializer )?
+            .into_iter()
+            .map(|key| key.0 )
+            .collect()
+    )
+}
+
+/// Get the DNAs that agents may migrate from (see [`MigrationProperties`])
+pub fn allowed_previous_dnas() -> ExternResult<Vec<DnaHash>> {
+    Ok( dna_properties_or_default::<MigrationProperties>()?.previous_dnas )
+}
+
+
+//
+// Chain Migration Validation
+//
+/// Validate a [`CloseChain`] action
+///
+/// A closed chain must say where the agent is migrating to.
+pub fn validate_close_chain(close_chain: &CloseChain) -> ExternResult<ValidateCallbackResult> {
+    if close_chain.new_target.is_none() {
+        return Ok(ValidateCallbackResult::Invalid(
+            "CloseChain must have a migration target".to_string()
+        ));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// Require that an action does not follow a [`CloseChain`]
+///
+/// Use when validating each action's agent activity so that `CloseChain` is always the last action
+/// on a chain.
+///
+/// ##### Example: Basic Usage
+/// ```
+/// # use hdi::prelude::*;
+/// # use hdi_extensions::*;
+///
+/// fn validate_activity(action: Action) -> ExternResult<ValidateCallbackResult> {
+///     require_chain_not_closed( &action )
+/// }
+/// ```
+pub fn require_chain_not_closed(action: &Action) -> ExternResult<ValidateCallbackResult> {
+    let prev_action = match action.prev_action() {
+        Some(prev_action) => prev_action,
+        None => return Ok(ValidateCallbackResult::Valid),
+    };
+
+    if let Action::CloseChain(_) = summon_action( prev_action )?.hashed.content {
+        return Ok(ValidateCallbackResult::Invalid(
+            format!("Chain was closed by action ({}); no actions can follow it", prev_action )
+        ));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// Validate an [`OpenChain`] action
+///
+/// The previous DNA must be listed in the DNA properties' `previous_dnas` (see
+/// [`MigrationProperties`]).  Agent migrations are not accepted.
+pub fn validate_open_chain(open_chain: &OpenChain) -> ExternResult<ValidateCallbackResult> {
+    let prev_dna = match &open_chain.prev_target {
+        MigrationTarget::Dna(dna_hash) => dna_hash,
+        MigrationTarget::Agent(agent) => return Ok(ValidateCallbackResult::Invalid(
+            format!("OpenChain must migrate from a DNA; not agent '{}'", agent )
+        )),
+    };
+
+    if !allowed_previous_dnas()?.contains( prev_dna ) {
+        return Ok(ValidateCallbackResult::Invalid(
+            format!("OpenChain previous DNA ({}) is not an allowed migration source", prev_dna )
+        ));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+
+//
+// Migration Boundary
+//
+/// Find the author's [`OpenChain`] action back from `from`
+pub fn find_open_chain(
+    author: &AgentPubKey,
+    from: &ActionHash,
+) -> ExternResult<Option<(ActionHash, OpenChain)>> {
+    Ok(
+        find_latest_action_of_type( author, from, &ActionType::OpenChain )?
+            .and_then(|activity| match activity.action.hashed.content {
+                Action::OpenChain(open_chain) => Some( (activity.action.hashed.hash, open_chain) ),
+                _ => None,
+            })
+    )
+}
+
+/// Get the previous DNA that the author migrated from (if any)
+pub fn migrated_from_dna(
+    author: &AgentPubKey,
+    from: &ActionHash,
+) -> ExternResult<Option<DnaHash>> {
+    Ok(
+        find_open_chain( author, from )?
+            .and_then(|(_, open_chain)| match open_chain.prev_target {
+                MigrationTarget::Dna(dna_hash) => Some( dna_hash ),
+                MigrationTarget::Agent(_) => None,
+            })
+    )
+}
+
+/// Validate a link that connects the author's entity to their entity in the previous DNA
+///
+/// The target is in another DNA so it cannot be fetched; instead, the link's author must have
+/// opened their chain by migrating from an allowed previous DNA.
+///
+/// ##### Example: Basic Usage
+/// ```
+/// # use hdi::prelude::*;
+/// # use hdi_extensions::*;
+///
+/// fn validate_create_link(create_link: CreateLink) -> ExternResult<ValidateCallbackResult> {
+///     validate_migration_link( &create_link )
+/// }
+/// ```
+pub fn validate_migration_link(create_link: &CreateLink) -> ExternResult<ValidateCallbackResult> {
+    let prev_dna = match migrated_from_dna( &create_link.author, &create_link.prev_action )? {
+        Some(prev_dna) => prev_dna,
+        None => return Ok(ValidateCallbackResult::Invalid(
+            format!("Author ({}) has not migrated from a previous DNA", create_link.author )
+        )),
+    };
+
+    if !allowed_previous_dnas()?.contains( &prev_dna ) {
+        return Ok(ValidateCallbackResult::Invalid(
+            format!("Author migrated from DNA ({}) which is not an allowed migration source", prev_dna )
+        ));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
@@ -0,0 +1,131 @@
+use core::convert::TryFrom;
+use hdi::prelude::{
+    ExternResult, WasmError,
+    Deserialize,
+    AgentPubKey,
+    Action, Create, EntryType,
+    ScopedEntryDefIndex,
+    ValidateCallbackResult,
+};
+use crate::{
+    guest_error,
+    dna_properties_or_default,
+    find_in_chain,
+    ProgenitorInvite,
+    ScopedTypeConnector,
+};
+use crate::properties::AgentKey;
+
+
+//
+// Properties
+//
+/// DNA properties following the `progenitor` convention
+///
+/// ```yaml
+/// properties:
+///   progenitor: uhCAk...
+/// ```
+#[derive(Deserialize, Default)]
+struct ProgenitorProperties {
+    #[serde(default)]
+    progenitor: Option<AgentKey>,
+}
+
+/// Get the progenitor from the DNA properties (if any)
+pub fn dna_progenitor() -> ExternResult<Option<AgentPubKey>> {
+    Ok(
+        dna_properties_or_default::<ProgenitorProperties>()?
+            .progenitor
+            .map(|key| key.0 )
+    )
+}
+
+/// Get the progenitor from the DNA properties or fail if there is none
+pub fn summon_progenitor() -> ExternResult<AgentPubKey> {
+    dna_progenitor()?
+        .ok_or(guest_error!("DNA properties do not define a progenitor"))
+}
+
+/// Check if an agent is the DNA's progenitor
+pub fn is_progenitor(agent: &AgentPubKey) -> ExternResult<bool> {
+    Ok( dna_progenitor()?.as_ref() == Some( agent ) )
+}
+
+impl ProgenitorInvite {
+    /// Accept invites signed by the progenitor from the DNA properties
+    pub fn from_properties() -> ExternResult<Self> {
+        Ok( ProgenitorInvite::new( summon_progenitor()? ) )
+    }
+}
+
+
+//
+// Validation
+//
+/// Require an action to be authored by the progenitor
+///
+/// ##### Example: Basic Usage
+/// ```
+/// # use hdi::prelude::*;
+/// # use hdi_extensions::*;
+///
+/// fn validate_create(create: Create) -> ExternResult<ValidateCallbackResult> {
+///     require_progenitor( &Action::Create( create ) )
+/// }
+/// ```
+pub fn require_progenitor(action: &Action) -> ExternResult<ValidateCallbackResult> {
+    let progenitor = summon_progenitor()?;
+
+    Ok(
+        match action.author() == &progenitor {
+            true => ValidateCallbackResult::Valid,
+            false => ValidateCallbackResult::Invalid(
+                format!("Only the progenitor ({}) can do this; not '{}'", progenitor, action.author() )
+            ),
+        }
+    )
+}
+
+/// Require the progenitor's first app entry to be a `T` (configuration) entry
+///
+/// Creates by other agents and the progenitor's later creates are always valid.
+///
+/// ##### Example: Basic Usage
+/// ```ignore
+/// use hdi::prelude::*;
+/// use hdi_extensions::*;
+///
+/// fn validate_create(create: Create) -> ExternResult<ValidateCallbackResult> {
+///     validate_progenitor_config::<ConfigEntry,_,_>( &create )
+/// }
+/// ```
+pub fn validate_progenitor_config<T,ET,ETU>(create: &Create) -> ExternResult<ValidateCallbackResult>
+where
+    T: ScopedTypeConnector<ET,ETU>,
+    ScopedEntryDefIndex: for<'a> TryFrom<&'a ET, Error = WasmError>,
+{
+    let app_entry_def = match &create.entry_type {
+        EntryType::App(app_entry_def) => app_entry_def,
+        _ => return Ok(ValidateCallbackResult::Valid),
+    };
+
+    if !is_progenitor( &create.author )? {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+
+    let previous_app_create = find_in_chain( &create.author, &create.prev_action, |activity| {
+        matches!(
+            &activity.action.hashed.content,
+            Action::Create(Create { entry_type: EntryType::App(_), .. })
+        )
+    })?;
+
+    if previous_app_create.is_none() && *app_entry_def != T::app_entry_def() {
+        return Ok(ValidateCallbackResult::Invalid(
+            "The progenitor's first app entry must be the configuration entry".to_string()
+        ));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
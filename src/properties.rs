@@ -0,0 +1,164 @@
+use core::fmt;
+use std::cell::RefCell;
+use hdi::prelude::{
+    dna_info,
+    ExternResult, WasmError,
+    Deserialize,
+    AgentPubKey,
+};
+use serde::de::DeserializeOwned;
+use crate::guest_error;
+
+
+thread_local! {
+    static PROPERTIES_CACHE : RefCell<Option<Vec<u8>>> = const { RefCell::new(None) };
+}
+
+// msgpack 'nil'
+const EMPTY_PROPERTIES : [u8; 1] = [ 0xc0 ];
+
+
+//
+// Errors
+//
+/// Reasons that DNA properties could not be loaded
+#[derive(Debug)]
+pub enum DnaPropertiesError {
+    /// The `dna_info` host call failed
+    Host(WasmError),
+    /// The DNA has no properties
+    Empty,
+    /// The properties do not match the expected struct
+    Deserialize(String),
+}
+
+impl fmt::Display for DnaPropertiesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DnaPropertiesError::Host(error) => write!(f, "Could not get DNA info: {:?}", error ),
+            DnaPropertiesError::Empty => write!(f, "DNA properties are empty"),
+            DnaPropertiesError::Deserialize(msg) => write!(f, "Could not deserialize DNA properties: {}", msg ),
+        }
+    }
+}
+
+impl From<DnaPropertiesError> for WasmError {
+    fn from(error: DnaPropertiesError) -> Self {
+        match error {
+            DnaPropertiesError::Host(error) => error,
+            error => guest_error!( error.to_string() ),
+        }
+    }
+}
+
+
+//
+// Loaders
+//
+/// Get the raw (msgpack) DNA properties
+///
+/// The bytes are cached after the first host call because properties cannot change for the
+/// lifetime of a DNA.
+pub fn dna_properties_bytes() -> Result<Vec<u8>, DnaPropertiesError> {
+    if let Some(bytes) = PROPERTIES_CACHE.with(|cache| cache.borrow().clone() ) {
+        return Ok( bytes );
+    }
+
+    let bytes = dna_info()
+        .map_err( DnaPropertiesError::Host )?
+        .modifiers.properties.bytes().to_owned();
+
+    PROPERTIES_CACHE.with(|cache| *cache.borrow_mut() = Some( bytes.clone() ) );
+
+    Ok( bytes )
+}
+
+/// Deserialize the DNA properties into the expected struct
+///
+/// ##### Example: Basic Usage
+/// ```
+/// # use hdi::prelude::*;
+/// # use hdi_extensions::*;
+///
+/// #[derive(Deserialize, Debug)]
+/// struct Properties {
+///     pub max_message_length: usize,
+/// }
+///
+/// fn test() -> ExternResult<()> {
+///     let properties : Properties = dna_properties()?;
+///     Ok(())
+/// }
+/// ```
+pub fn dna_properties<T>() -> Result<T, DnaPropertiesError>
+where
+    T: DeserializeOwned,
+{
+    let bytes = dna_properties_bytes()?;
+
+    if bytes.is_empty() || bytes == EMPTY_PROPERTIES {
+        return Err( DnaPropertiesError::Empty );
+    }
+
+    rmp_serde::from_slice( &bytes )
+        .map_err(|err| DnaPropertiesError::Deserialize( err.to_string() ) )
+}
+
+/// Deserialize the DNA properties or use the default when there are none
+pub fn dna_properties_or_default<T>() -> Result<T, DnaPropertiesError>
+where
+    T: DeserializeOwned + Default,
+{
+    match dna_properties() {
+        Err(DnaPropertiesError::Empty) => Ok( T::default() ),
+        result => result,
+    }
+}
+
+
+//
+// Conventions
+//
+/// Agent key that can be written as a HoloHash string
+#[derive(Deserialize)]
+pub(crate) struct AgentKey(
+    #[serde(with = "crate::hash_serde::agent_pubkey")]
+    pub(crate) AgentPubKey
+);
+
+/// DNA properties following the `admins` convention
+///
+/// Admin keys can be written as HoloHash strings in the DNA manifest.
+///
+/// ```yaml
+/// properties:
+///   admins:
+///     - uhCAk...
+/// ```
+#[derive(Deserialize, Debug, Default)]
+pub struct AdminProperties {
+    #[serde(default, deserialize_with = "deserialize_agent_keys")]
+    pub admins: Vec<AgentPubKey>,
+}
+
+fn deserialize_agent_keys<'de, D>(deserializer: D) -> Result<Vec<AgentPubKey>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Ok(
+        Vec::<AgentKey>::deserialize( deserializer )?
+            .into_iter()
+            .map(|key| key.0 )
+            .collect()
+    )
+}
+
+/// Get the admin agents from the DNA properties (see [`AdminProperties`])
+pub fn dna_admins() -> ExternResult<Vec<AgentPubKey>> {
+    Ok( dna_properties_or_default::<AdminProperties>()?.admins )
+}
+
+/// Check if an agent is listed in the DNA properties' `admins`
+pub fn is_admin(agent: &AgentPubKey) -> ExternResult<bool> {
+    Ok( dna_admins()?.contains( agent ) )
+}
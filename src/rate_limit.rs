@@ -0,0 +1,108 @@
+use core::convert::TryFrom;
+use core::time::Duration;
+use hdi::prelude::{
+    ExternResult, WasmError,
+    Timestamp,
+    Action, EntryCreationAction, EntryType,
+    ScopedEntryDefIndex,
+    ValidateCallbackResult,
+};
+use crate::{
+    detect_app_entry_unit,
+    walk_chain_back,
+};
+
+
+//
+// Rate Limit
+//
+/// Limit how many creates of an entry type an agent can make within a time window
+///
+/// ##### Example: Basic Usage
+/// ```ignore
+/// use hdi::prelude::*;
+/// use hdi_extensions::*;
+///
+/// fn validate_create_post(create: Create) -> ExternResult<ValidateCallbackResult> {
+///     RateLimit::new( EntryTypesUnit::Post, 20, core::time::Duration::from_secs(3600) )
+///         .check( &create )
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct RateLimit<ETU> {
+    pub entry_unit: ETU,
+    pub max: u32,
+    pub window: Duration,
+}
+
+impl<ETU> RateLimit<ETU>
+where
+    ETU: TryFrom<ScopedEntryDefIndex, Error = WasmError> + PartialEq + core::fmt::Debug,
+{
+    /// Create a rate limit rule
+    pub fn new(entry_unit: ETU, max: u32, window: Duration) -> Self {
+        RateLimit { entry_unit, max, window }
+    }
+
+    fn matches_unit<A>(&self, action: &A) -> bool
+    where
+        A: Into<EntryCreationAction> + Clone,
+    {
+        let action : EntryCreationAction = action.to_owned().into();
+
+        matches!( action.entry_type(), EntryType::App(_) )
+            && matches!( detect_app_entry_unit::<ETU,_>( &action ), Ok(unit) if unit == self.entry_unit )
+    }
+
+    /// Check a new creation action against the limit
+    ///
+    /// Only `Create` actions are limited and counted; updates and actions of other entry types are
+    /// always valid.  The author's chain is walked back from the
+    /// action's `prev_action` while timestamps are within the window, so every validator reaches
+    /// the same result.
+    pub fn check<A>(&self, action: &A) -> ExternResult<ValidateCallbackResult>
+    where
+        A: Into<EntryCreationAction> + Clone,
+    {
+        let action : EntryCreationAction = action.to_owned().into();
+
+        if !matches!( action, EntryCreationAction::Create(_) ) || !self.matches_unit( &action ) {
+            return Ok(ValidateCallbackResult::Valid);
+        }
+
+        let window_micros = i64::try_from( self.window.as_micros() ).unwrap_or( i64::MAX );
+        let window_start = Timestamp::from_micros(
+            action.timestamp().as_micros().saturating_sub( window_micros )
+        );
+        // Include the action being validated
+        let mut count = 1;
+
+        walk_chain_back( action.author(), action.prev_action(), |activity| {
+            let previous = &activity.action.hashed.content;
+
+            if previous.timestamp() < window_start {
+                return Ok( false );
+            }
+
+            if let Action::Create(create) = previous {
+                if self.matches_unit( create ) {
+                    count += 1;
+                }
+            }
+
+            Ok( count <= self.max )
+        })?;
+
+        Ok(
+            match count > self.max {
+                true => ValidateCallbackResult::Invalid(
+                    format!(
+                        "Rate limit exceeded for {:?}: more than {} creates within {} seconds",
+                        self.entry_unit, self.max, self.window.as_secs(),
+                    )
+                ),
+                false => ValidateCallbackResult::Valid,
+            }
+        )
+    }
+}
@@ -0,0 +1,371 @@
+use hdi::prelude::{
+    hdk_entry_helper, wasm_error, holochain_serial,
+    ExternResult, WasmError, WasmErrorInner,
+    Deserialize, Serialize,
+    SerializedBytes, AppEntryBytes, RecordEntry, EntryError,
+    ActionHash, AgentPubKey, Timestamp,
+    Action, Create, Delete, DeleteLink, EntryType,
+    ScopedEntryDefIndex,
+    ValidateCallbackResult,
+};
+use crate::{
+    guest_error,
+    dna_admins,
+    is_admin,
+    is_link_creator,
+    summon_action,
+    summon_entry,
+    summon_valid_record,
+    walk_chain_back,
+    ScopedTypeConnector,
+};
+
+
+//
+// Role Entries
+//
+/// Grants a role to an agent
+///
+/// Include this in the zome's entry types to use role based authorization.  Role checks need to
+/// know their entry defs, so connect both role entries with
+/// [`scoped_type_connector!`](crate::scoped_type_connector).
+///
+/// ##### Example: Basic Usage
+/// ```ignore
+/// use hdi::prelude::*;
+/// use hdi_extensions::*;
+///
+/// #[hdk_entry_types]
+/// #[unit_enum(EntryTypesUnit)]
+/// pub enum EntryTypes {
+///     #[entry_type]
+///     RoleGrant(RoleGrantEntry),
+///     #[entry_type]
+///     RoleRevoke(RoleRevokeEntry),
+/// }
+///
+/// scoped_type_connector!( EntryTypesUnit::RoleGrant, EntryTypes::RoleGrant( RoleGrantEntry ) );
+/// scoped_type_connector!( EntryTypesUnit::RoleRevoke, EntryTypes::RoleRevoke( RoleRevokeEntry ) );
+/// ```
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct RoleGrantEntry {
+    pub role: String,
+    pub agent: AgentPubKey,
+    /// The grant stops applying at this time (exclusive)
+    pub expires_at: Option<Timestamp>,
+}
+
+/// Revokes a previous [`RoleGrantEntry`]
+///
+/// A revoke must be newer than the grant it revokes (see [`validate_role_revoke`]).
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct RoleRevokeEntry {
+    /// Address of the grant's `Create` action
+    pub grant: ActionHash,
+}
+
+
+//
+// Entry Validation
+//
+/// Validate the creation of a [`RoleGrantEntry`]
+///
+/// Only DNA admins (see [`is_admin`]) may grant roles.
+pub fn validate_role_grant(
+    grant: &RoleGrantEntry,
+    create: &Create,
+) -> ExternResult<ValidateCallbackResult> {
+    if grant.role.is_empty() {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Role grant must name a role".to_string()
+        ));
+    }
+
+    if !is_admin( &create.author )? {
+        return Ok(ValidateCallbackResult::Invalid(
+            format!("Only admins can grant roles; not '{}'", create.author )
+        ));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// Validate the creation of a [`RoleRevokeEntry`]
+///
+/// Only DNA admins or the original grantor may revoke a role, and the revoke must be newer than
+/// the grant.
+pub fn validate_role_revoke<ET,ETU>(
+    revoke: &RoleRevokeEntry,
+    create: &Create,
+) -> ExternResult<ValidateCallbackResult>
+where
+    RoleGrantEntry: ScopedTypeConnector<ET,ETU>,
+    ScopedEntryDefIndex: for<'a> TryFrom<&'a ET, Error = WasmError>,
+{
+    let grant_record = summon_valid_record( &revoke.grant )?;
+
+    if !RoleGrantEntry::check_record_entry_type( &grant_record ) {
+        return Ok(ValidateCallbackResult::Invalid(
+            format!("Role revoke must reference a role grant; not '{}'", revoke.grant )
+        ));
+    }
+
+    let grantor = grant_record.action().author();
+
+    if grantor != &create.author && !is_admin( &create.author )? {
+        return Ok(ValidateCallbackResult::Invalid(
+            format!("Only admins or the grantor ({}) can revoke a role; not '{}'", grantor, create.author )
+        ));
+    }
+
+    if create.timestamp <= grant_record.action().timestamp() {
+        return Ok(ValidateCallbackResult::Invalid(
+            format!("Role revoke must be newer than the grant ({})", revoke.grant )
+        ));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+
+//
+// Role Resolution
+//
+/// Proof that an agent held a role
+///
+/// Grants are only counted when they are valid admin grants, and revokes are searched for on
+/// every admin's chain, so the evidence does not need to come from a trusted source.
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq)]
+pub struct RoleEvidence {
+    /// [`RoleGrantEntry`] create actions
+    pub grants: Vec<ActionHash>,
+    /// One action per DNA admin made at or after the time being checked (see
+    /// [`find_grant_revoke`])
+    pub checkpoints: Vec<ActionHash>,
+}
+
+/// Determine if an agent held a role at the given time
+///
+/// A grant counts when it was created by an admin at or before `at`, has not expired, and has not
+/// been revoked at or before `at`.  Grants that do not have [`RoleGrantEntry`]'s entry def are an
+/// error.  When a matching grant exists, `evidence` must have a checkpoint for every DNA admin;
+/// so a check cannot complete until each admin has made an action at or after `at`.
+pub fn held_role_at<ET,ETU>(
+    agent: &AgentPubKey,
+    role: &str,
+    at: &Timestamp,
+    evidence: &RoleEvidence,
+) -> ExternResult<bool>
+where
+    RoleGrantEntry: ScopedTypeConnector<ET,ETU>,
+    RoleRevokeEntry: ScopedTypeConnector<ET,ETU>,
+    ScopedEntryDefIndex: for<'a> TryFrom<&'a ET, Error = WasmError>,
+{
+    let mut candidates = vec![];
+
+    for addr in &evidence.grants {
+        let record = summon_valid_record( addr )?;
+        let action = record.action().to_owned();
+
+        if !matches!( action, Action::Create(_) ) {
+            Err(guest_error!(
+                "Role evidence ({}) is not a role grant", addr
+            ))?
+        }
+
+        let grant = RoleGrantEntry::try_from_record( &record )?;
+
+        if grant.agent != *agent || grant.role != role || action.timestamp() > *at {
+            continue;
+        }
+
+        if matches!( grant.expires_at, Some(expires_at) if expires_at <= *at ) {
+            continue;
+        }
+
+        if is_admin( action.author() )? {
+            candidates.push( addr );
+        }
+    }
+
+    if candidates.is_empty() {
+        return Ok( false );
+    }
+
+    let mut checkpoint_authors = vec![];
+
+    for checkpoint in &evidence.checkpoints {
+        checkpoint_authors.push( summon_action( checkpoint )?.hashed.content.author().to_owned() );
+    }
+
+    for admin in dna_admins()? {
+        if !checkpoint_authors.contains( &admin ) {
+            Err(guest_error!(
+                "Role evidence is missing a checkpoint for admin ({})", admin
+            ))?
+        }
+    }
+
+    for grant in candidates {
+        let mut revoked = false;
+
+        for checkpoint in &evidence.checkpoints {
+            if find_grant_revoke::<ET,ETU>( grant, checkpoint, at )?.is_some() {
+                revoked = true;
+                break;
+            }
+        }
+
+        if !revoked {
+            return Ok( true );
+        }
+    }
+
+    Ok( false )
+}
+
+/// Find a revoke of a role grant made at or before `at` on the checkpoint author's chain
+///
+/// Walks back from `checkpoint` until the grant (or anything older) is reached.  The checkpoint
+/// must be at or after `at` so that no earlier revoke is skipped.  Returns the revoke's action
+/// address; revokes by authors who are neither the grantor nor an admin are ignored, matching
+/// [`validate_role_revoke`].
+pub fn find_grant_revoke<ET,ETU>(
+    grant: &ActionHash,
+    checkpoint: &ActionHash,
+    at: &Timestamp,
+) -> ExternResult<Option<ActionHash>>
+where
+    RoleRevokeEntry: ScopedTypeConnector<ET,ETU>,
+    ScopedEntryDefIndex: for<'a> TryFrom<&'a ET, Error = WasmError>,
+{
+    let grant_action = summon_action( grant )?.hashed.content;
+    let checkpoint_action = summon_action( checkpoint )?.hashed.content;
+    let author = checkpoint_action.author().to_owned();
+
+    if checkpoint_action.timestamp() < *at {
+        Err(guest_error!(
+            "Checkpoint ({}) is older than {}; later revokes would be missed", checkpoint, at.as_micros()
+        ))?
+    }
+
+    if &author != grant_action.author() && !is_admin( &author )? {
+        return Ok( None );
+    }
+
+    let revoke_type = EntryType::App( RoleRevokeEntry::app_entry_def() );
+    let mut found = None;
+
+    walk_chain_back( &author, checkpoint, |activity| {
+        let action = &activity.action.hashed;
+
+        if action.hash == *grant || action.content.timestamp() < grant_action.timestamp() {
+            return Ok( false );
+        }
+
+        if let Action::Create(create) = &action.content {
+            if create.entry_type != revoke_type || create.timestamp > *at {
+                return Ok( true );
+            }
+
+            let entry = match &activity.cached_entry {
+                Some(entry) => entry.to_owned(),
+                None => summon_entry( &create.entry_hash )?.content,
+            };
+
+            if RoleRevokeEntry::try_from( entry )?.grant == *grant {
+                found = Some( action.hash.to_owned() );
+                return Ok( false );
+            }
+        }
+
+        Ok( true )
+    })?;
+
+    Ok( found )
+}
+
+/// Require an agent to have held a role at the given time (see [`held_role_at`])
+pub fn require_role<ET,ETU>(
+    agent: &AgentPubKey,
+    role: &str,
+    at: &Timestamp,
+    evidence: &RoleEvidence,
+) -> ExternResult<ValidateCallbackResult>
+where
+    RoleGrantEntry: ScopedTypeConnector<ET,ETU>,
+    RoleRevokeEntry: ScopedTypeConnector<ET,ETU>,
+    ScopedEntryDefIndex: for<'a> TryFrom<&'a ET, Error = WasmError>,
+{
+    Ok(
+        match held_role_at::<ET,ETU>( agent, role, at, evidence )? {
+            true => ValidateCallbackResult::Valid,
+            false => ValidateCallbackResult::Invalid(
+                format!("Agent ({}) did not hold role '{}' at {}", agent, role, at.as_micros() )
+            ),
+        }
+    )
+}
+
+/// Require a [`Delete`] to be made by the original author or an agent holding the role
+///
+/// A `Delete` carries no payload, so the zome must get `evidence` from its own records (eg. an
+/// entry committed alongside the delete).  See [`held_role_at`] for how it is checked.
+///
+/// ##### Example: Basic Usage
+/// ```ignore
+/// use hdi::prelude::*;
+/// use hdi_extensions::*;
+///
+/// fn validate_delete(delete: Delete, evidence: RoleEvidence) -> ExternResult<ValidateCallbackResult> {
+///     require_author_or_role::<EntryTypes,EntryTypesUnit>( &delete, "moderator", &evidence )
+/// }
+/// ```
+pub fn require_author_or_role<ET,ETU>(
+    delete: &Delete,
+    role: &str,
+    evidence: &RoleEvidence,
+) -> ExternResult<ValidateCallbackResult>
+where
+    RoleGrantEntry: ScopedTypeConnector<ET,ETU>,
+    RoleRevokeEntry: ScopedTypeConnector<ET,ETU>,
+    ScopedEntryDefIndex: for<'a> TryFrom<&'a ET, Error = WasmError>,
+{
+    let original_author = summon_action( &delete.deletes_address )?.hashed.content.author().to_owned();
+
+    if original_author == delete.author
+        || held_role_at::<ET,ETU>( &delete.author, role, &delete.timestamp, evidence )?
+    {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+
+    Ok(ValidateCallbackResult::Invalid(
+        format!("Only the author ({}) or a '{}' can delete; not '{}'", original_author, role, delete.author )
+    ))
+}
+
+/// Require a [`DeleteLink`] to be made by the link creator or an agent holding the role
+///
+/// See [`require_author_or_role`] for where `evidence` comes from.
+pub fn require_link_creator_or_role<ET,ETU>(
+    delete_link: &DeleteLink,
+    role: &str,
+    evidence: &RoleEvidence,
+) -> ExternResult<ValidateCallbackResult>
+where
+    RoleGrantEntry: ScopedTypeConnector<ET,ETU>,
+    RoleRevokeEntry: ScopedTypeConnector<ET,ETU>,
+    ScopedEntryDefIndex: for<'a> TryFrom<&'a ET, Error = WasmError>,
+{
+    if is_link_creator( delete_link )?
+        || held_role_at::<ET,ETU>( &delete_link.author, role, &delete_link.timestamp, evidence )?
+    {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+
+    Ok(ValidateCallbackResult::Invalid(
+        format!("Only the link creator or a '{}' can delete a link; not '{}'", role, delete_link.author )
+    ))
+}
@@ -0,0 +1,159 @@
+use core::fmt::Debug;
+use hdi::prelude::{
+    ExternResult,
+    Deserialize, Serialize,
+    AgentPubKey, Signature,
+    ValidateCallbackResult,
+};
+use hdi::ed25519::verify_signature;
+
+
+//
+// Signed Payloads
+//
+/// A payload with a detached signature from another agent
+///
+/// The signature is made over the msgpack serialization of `payload` (eg. `sign( agent, payload )`).
+///
+/// ##### Example: Basic Usage
+/// ```
+/// # use hdi::prelude::*;
+/// # use hdi_extensions::*;
+///
+/// #[derive(Serialize, Deserialize, Debug)]
+/// struct Approval {
+///     pub proposal: ActionHash,
+/// }
+///
+/// fn check(approval: SignedPayload<Approval>, approver: AgentPubKey) -> ExternResult<ValidateCallbackResult> {
+///     approval.require_signer( &approver )
+/// }
+/// ```
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct SignedPayload<T> {
+    pub payload: T,
+    pub signature: Signature,
+}
+
+impl<T> SignedPayload<T>
+where
+    T: Serialize + Debug,
+{
+    /// Wrap a payload and its signature
+    pub fn new(payload: T, signature: Signature) -> Self {
+        SignedPayload { payload, signature }
+    }
+
+    /// Check if the signature was made by `signer`
+    pub fn verify(&self, signer: &AgentPubKey) -> ExternResult<bool> {
+        verify_signature( signer.to_owned(), self.signature.to_owned(), &self.payload )
+    }
+
+    /// Require the signature to be made by `signer`
+    pub fn require_signer(&self, signer: &AgentPubKey) -> ExternResult<ValidateCallbackResult> {
+        Ok(
+            match self.verify( signer )? {
+                true => ValidateCallbackResult::Valid,
+                false => ValidateCallbackResult::Invalid(
+                    format!("Payload signature is not from agent '{}'", signer )
+                ),
+            }
+        )
+    }
+}
+
+
+//
+// Multiple Signers
+//
+/// An agent's signature of a shared payload
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct AgentSignature {
+    pub agent: AgentPubKey,
+    pub signature: Signature,
+}
+
+/// Get the agents whose signatures of `payload` are valid
+///
+/// Invalid signatures are ignored.
+pub fn verified_signers<T>(
+    payload: &T,
+    signatures: &[AgentSignature],
+) -> ExternResult<Vec<AgentPubKey>>
+where
+    T: Serialize + Debug,
+{
+    let mut signers = vec![];
+
+    for agent_signature in signatures {
+        if verify_signature( agent_signature.agent.to_owned(), agent_signature.signature.to_owned(), payload )? {
+            signers.push( agent_signature.agent.to_owned() );
+        }
+    }
+
+    Ok( signers )
+}
+
+/// Require every agent in `required` to have a valid signature of `payload`
+///
+/// ##### Example: Basic Usage
+/// ```
+/// # use hdi::prelude::*;
+/// # use hdi_extensions::*;
+///
+/// fn check(
+///     proposal: ActionHash,
+///     signatures: Vec<AgentSignature>,
+///     board: Vec<AgentPubKey>,
+/// ) -> ExternResult<ValidateCallbackResult> {
+///     require_signers( &proposal, &signatures, &board )
+/// }
+/// ```
+pub fn require_signers<T>(
+    payload: &T,
+    signatures: &[AgentSignature],
+    required: &[AgentPubKey],
+) -> ExternResult<ValidateCallbackResult>
+where
+    T: Serialize + Debug,
+{
+    let signers = verified_signers( payload, signatures )?;
+    let missing : Vec<String> = required.iter()
+        .filter(|agent| !signers.contains( agent ) )
+        .map(|agent| agent.to_string() )
+        .collect();
+
+    Ok(
+        match missing.is_empty() {
+            true => ValidateCallbackResult::Valid,
+            false => ValidateCallbackResult::Invalid(
+                format!("Missing valid signatures from required signers: {}", missing.join(", ") )
+            ),
+        }
+    )
+}
+
+/// Require at least `threshold` distinct agents in `signers` to have a valid signature of `payload`
+pub fn require_signature_threshold<T>(
+    payload: &T,
+    signatures: &[AgentSignature],
+    signers: &[AgentPubKey],
+    threshold: usize,
+) -> ExternResult<ValidateCallbackResult>
+where
+    T: Serialize + Debug,
+{
+    let mut verified = verified_signers( payload, signatures )?;
+    verified.retain(|agent| signers.contains( agent ) );
+    verified.sort();
+    verified.dedup();
+
+    Ok(
+        match verified.len() >= threshold {
+            true => ValidateCallbackResult::Valid,
+            false => ValidateCallbackResult::Invalid(
+                format!("Payload has {} valid signatures from the signer list; {} required", verified.len(), threshold )
+            ),
+        }
+    )
+}
@@ -0,0 +1,143 @@
+use core::convert::TryFrom;
+use hdi::prelude::{
+    ExternResult, WasmError, WasmErrorInner,
+    ActionHash, EntryHash, AnyDhtHash, AnyLinkableHash,
+    Record, Entry, SignedActionHashed, EntryHashed, EntryCreationAction, ActionType,
+    ValidateCallbackResult, UnresolvedDependencies,
+};
+use holo_hash::AnyLinkableHashPrimitive;
+use crate::{
+    summon_valid_record,
+    summon_action,
+    summon_entry,
+    summon_app_entry,
+    summon_record_type,
+    summon_creation_action,
+};
+
+
+//
+// Summoned
+//
+/// Outcome of fetching a validation dependency
+///
+/// Separates "cannot validate yet" from "invalid" so that each maps to the right
+/// [`ValidateCallbackResult`] (see [`validation_result!`](crate::validation_result)).
+#[derive(Clone, Debug, PartialEq)]
+pub enum Summoned<T> {
+    /// The dependency was found and is what we expected
+    Found(T),
+    /// The dependency is not available yet
+    Unresolved(UnresolvedDependencies),
+    /// The dependency was found but is not what we expected
+    Invalid(String),
+}
+
+impl<T> Summoned<T> {
+    /// Classify the result of a `summon_*` call
+    ///
+    /// - `Ok` is [`Summoned::Found`]
+    /// - A host short-circuit (raised by `must_get_*` when a dependency is missing) is
+    ///   [`Summoned::Unresolved`] for the given hashes
+    /// - A guest error (eg. wrong action type or entry struct) is [`Summoned::Invalid`]
+    /// - Any other error is returned as an error
+    pub fn from_result(result: ExternResult<T>, hashes: Vec<AnyDhtHash>) -> ExternResult<Self> {
+        match result {
+            Ok(value) => Ok( Summoned::Found( value ) ),
+            Err(WasmError { error: WasmErrorInner::HostShortCircuit(_), .. }) => Ok(
+                Summoned::Unresolved( UnresolvedDependencies::Hashes( hashes ) )
+            ),
+            Err(WasmError { error: WasmErrorInner::Guest(msg), .. }) => Ok( Summoned::Invalid( msg ) ),
+            Err(error) => Err( error ),
+        }
+    }
+
+    /// Check if the dependency was found
+    pub fn is_found(&self) -> bool {
+        matches!( self, Summoned::Found(_) )
+    }
+
+    /// Get the found value, if any
+    pub fn found(self) -> Option<T> {
+        match self {
+            Summoned::Found(value) => Some( value ),
+            _ => None,
+        }
+    }
+
+    /// Transform the found value
+    pub fn map<U, F>(self, op: F) -> Summoned<U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        match self {
+            Summoned::Found(value) => Summoned::Found( op( value ) ),
+            Summoned::Unresolved(deps) => Summoned::Unresolved( deps ),
+            Summoned::Invalid(msg) => Summoned::Invalid( msg ),
+        }
+    }
+
+    /// Convert into the [`ValidateCallbackResult`] that ends validation
+    ///
+    /// [`Summoned::Found`] becomes [`ValidateCallbackResult::Valid`].
+    pub fn into_validate_callback_result(self) -> ValidateCallbackResult {
+        match self {
+            Summoned::Found(_) => ValidateCallbackResult::Valid,
+            Summoned::Unresolved(deps) => ValidateCallbackResult::UnresolvedDependencies( deps ),
+            Summoned::Invalid(msg) => ValidateCallbackResult::Invalid( msg ),
+        }
+    }
+}
+
+
+//
+// Summon Variants
+//
+/// [`summon_valid_record`] as a [`Summoned`]
+pub fn try_summon_valid_record(action_hash: &ActionHash) -> ExternResult<Summoned<Record>> {
+    Summoned::from_result( summon_valid_record( action_hash ), vec![ action_hash.to_owned().into() ] )
+}
+
+/// [`summon_action`] as a [`Summoned`]
+pub fn try_summon_action(action_hash: &ActionHash) -> ExternResult<Summoned<SignedActionHashed>> {
+    Summoned::from_result( summon_action( action_hash ), vec![ action_hash.to_owned().into() ] )
+}
+
+/// [`summon_entry`] as a [`Summoned`]
+pub fn try_summon_entry(entry_hash: &EntryHash) -> ExternResult<Summoned<EntryHashed>> {
+    Summoned::from_result( summon_entry( entry_hash ), vec![ entry_hash.to_owned().into() ] )
+}
+
+/// [`summon_record_type`] as a [`Summoned`]
+pub fn try_summon_record_type(
+    action_hash: &ActionHash,
+    action_type: &ActionType,
+) -> ExternResult<Summoned<Record>> {
+    Summoned::from_result(
+        summon_record_type( action_hash, action_type ),
+        vec![ action_hash.to_owned().into() ],
+    )
+}
+
+/// [`summon_creation_action`] as a [`Summoned`]
+pub fn try_summon_creation_action(action_hash: &ActionHash) -> ExternResult<Summoned<EntryCreationAction>> {
+    Summoned::from_result( summon_creation_action( action_hash ), vec![ action_hash.to_owned().into() ] )
+}
+
+/// [`summon_app_entry`] as a [`Summoned`]
+///
+/// External hashes are [`Summoned::Invalid`] because they can never resolve to an entry.
+pub fn try_summon_app_entry<T,E>(addr: &AnyLinkableHash) -> ExternResult<Summoned<T>>
+where
+    T: TryFrom<Record, Error = E> + TryFrom<Entry, Error = E>,
+    E: std::fmt::Debug,
+    WasmError: From<E>,
+{
+    let hashes = match addr.to_owned().into_primitive() {
+        AnyLinkableHashPrimitive::Action(action_hash) => vec![ action_hash.into() ],
+        AnyLinkableHashPrimitive::Entry(entry_hash) => vec![ entry_hash.into() ],
+        AnyLinkableHashPrimitive::External(_) => vec![],
+    };
+
+    Summoned::from_result( summon_app_entry( addr ), hashes )
+}
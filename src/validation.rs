@@ -0,0 +1,211 @@
+use hdi::prelude::{
+    ExternResult,
+    ValidateCallbackResult, UnresolvedDependencies,
+};
+
+
+//
+// Validation
+//
+/// Composable wrapper around [`ValidateCallbackResult`]
+///
+/// - `Invalid` short-circuits and wins over everything else
+/// - `UnresolvedDependencies` hash lists are merged
+/// - `Valid` is the identity
+///
+/// ##### Example: Basic Usage
+/// ```
+/// # use hdi::prelude::*;
+/// # use hdi_extensions::*;
+///
+/// fn check_author(action: &Action) -> ExternResult<Validation> {
+///     Ok( Validation::check( action.action_seq() > 3, "Author's chain is too short" ) )
+/// }
+///
+/// fn check_message(message: &str) -> ExternResult<Validation> {
+///     Ok( Validation::check( !message.is_empty(), "Message cannot be empty" ) )
+/// }
+///
+/// fn validate_post(action: Action, message: String) -> ExternResult<ValidateCallbackResult> {
+///     Ok(
+///         check_author( &action )?.with_context("while checking author")
+///             .and_then(|| check_message( &message ) )?
+///             .into()
+///     )
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Validation(pub ValidateCallbackResult);
+
+impl Validation {
+    /// A valid result
+    pub fn valid() -> Self {
+        Validation( ValidateCallbackResult::Valid )
+    }
+
+    /// An invalid result
+    pub fn invalid<S>(message: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Validation( ValidateCallbackResult::Invalid( message.into() ) )
+    }
+
+    /// An unresolved result
+    pub fn unresolved(dependencies: UnresolvedDependencies) -> Self {
+        Validation( ValidateCallbackResult::UnresolvedDependencies( dependencies ) )
+    }
+
+    /// Valid if the condition is true, otherwise invalid with the given message
+    pub fn check<S>(condition: bool, message: S) -> Self
+    where
+        S: Into<String>,
+    {
+        match condition {
+            true => Self::valid(),
+            false => Self::invalid( message ),
+        }
+    }
+
+    /// Check if this is [`ValidateCallbackResult::Valid`]
+    pub fn is_valid(&self) -> bool {
+        matches!( self.0, ValidateCallbackResult::Valid )
+    }
+
+    /// Check if this is [`ValidateCallbackResult::Invalid`]
+    pub fn is_invalid(&self) -> bool {
+        matches!( self.0, ValidateCallbackResult::Invalid(_) )
+    }
+
+    /// Check if this is [`ValidateCallbackResult::UnresolvedDependencies`]
+    pub fn is_unresolved(&self) -> bool {
+        matches!( self.0, ValidateCallbackResult::UnresolvedDependencies(_) )
+    }
+
+    /// Get the wrapped [`ValidateCallbackResult`]
+    pub fn into_inner(self) -> ValidateCallbackResult {
+        self.0
+    }
+
+    /// Combine with another result (see the rules on [`Validation`])
+    pub fn merge(self, other: Validation) -> Validation {
+        match (self.0, other.0) {
+            (ValidateCallbackResult::Invalid(msg), _)
+                | (_, ValidateCallbackResult::Invalid(msg)) => Validation::invalid( msg ),
+            (ValidateCallbackResult::UnresolvedDependencies(a), ValidateCallbackResult::UnresolvedDependencies(b)) =>
+                Validation::unresolved( merge_dependencies( a, b ) ),
+            (ValidateCallbackResult::UnresolvedDependencies(deps), ValidateCallbackResult::Valid)
+                | (ValidateCallbackResult::Valid, ValidateCallbackResult::UnresolvedDependencies(deps)) =>
+                Validation::unresolved( deps ),
+            (ValidateCallbackResult::Valid, ValidateCallbackResult::Valid) => Validation::valid(),
+        }
+    }
+
+    /// Run the next check unless this result is invalid
+    pub fn and_then<F>(self, op: F) -> ExternResult<Validation>
+    where
+        F: FnOnce() -> ExternResult<Validation>,
+    {
+        Ok(
+            match self.is_invalid() {
+                true => self,
+                false => self.merge( op()? ),
+            }
+        )
+    }
+
+    /// Prefix an invalid message with context
+    pub fn with_context<S>(self, context: S) -> Validation
+    where
+        S: AsRef<str>,
+    {
+        match self.0 {
+            ValidateCallbackResult::Invalid(msg) => Validation::invalid(
+                format!("{}: {}", context.as_ref(), msg )
+            ),
+            other => Validation( other ),
+        }
+    }
+
+    /// Require every result to be valid
+    ///
+    /// Stops at the first invalid result, so a lazy iterator will not evaluate later checks.
+    pub fn all<I>(results: I) -> Validation
+    where
+        I: IntoIterator<Item = Validation>,
+    {
+        let mut combined = Validation::valid();
+
+        for result in results {
+            combined = combined.merge( result );
+
+            if combined.is_invalid() {
+                break;
+            }
+        }
+
+        combined
+    }
+
+    /// Require at least one result to be valid
+    ///
+    /// Stops at the first valid result.  Without a valid result, unresolved dependencies are
+    /// merged; otherwise the invalid messages are joined.
+    pub fn any<I>(results: I) -> Validation
+    where
+        I: IntoIterator<Item = Validation>,
+    {
+        let mut unresolved : Option<UnresolvedDependencies> = None;
+        let mut messages = vec![];
+
+        for result in results {
+            match result.0 {
+                ValidateCallbackResult::Valid => return Validation::valid(),
+                ValidateCallbackResult::UnresolvedDependencies(deps) => {
+                    unresolved = Some( match unresolved {
+                        Some(existing) => merge_dependencies( existing, deps ),
+                        None => deps,
+                    });
+                },
+                ValidateCallbackResult::Invalid(msg) => messages.push( msg ),
+            }
+        }
+
+        match unresolved {
+            Some(deps) => Validation::unresolved( deps ),
+            None if messages.is_empty() => Validation::invalid("No checks to satisfy"),
+            None => Validation::invalid( messages.join("; ") ),
+        }
+    }
+}
+
+impl From<ValidateCallbackResult> for Validation {
+    fn from(result: ValidateCallbackResult) -> Self {
+        Validation( result )
+    }
+}
+
+impl From<Validation> for ValidateCallbackResult {
+    fn from(validation: Validation) -> Self {
+        validation.0
+    }
+}
+
+/// Merge two sets of unresolved dependencies
+///
+/// Hash lists are combined without duplicates.  Agent activity dependencies cannot be combined, so
+/// the first agent activity dependency is kept over any hash list.
+fn merge_dependencies(a: UnresolvedDependencies, b: UnresolvedDependencies) -> UnresolvedDependencies {
+    match (a, b) {
+        (UnresolvedDependencies::Hashes(mut hashes), UnresolvedDependencies::Hashes(others)) => {
+            for hash in others {
+                if !hashes.contains( &hash ) {
+                    hashes.push( hash );
+                }
+            }
+            UnresolvedDependencies::Hashes( hashes )
+        },
+        (UnresolvedDependencies::Hashes(_), other @ UnresolvedDependencies::AgentActivity(..)) => other,
+        (first, _) => first,
+    }
+}
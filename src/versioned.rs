@@ -0,0 +1,208 @@
+use core::convert::TryFrom;
+use hdi::prelude::{
+    ExternResult, WasmError,
+    AnyLinkableHash,
+    Record, Entry,
+    ValidateCallbackResult,
+};
+use crate::{
+    guest_error,
+    summon_app_entry,
+};
+
+
+//
+// Versioned Entries
+//
+/// An app entry stored as one of several schema versions
+///
+/// Older versions are upgraded one step at a time (`V1 -> V2 -> V3`) until the latest version is
+/// reached.  Implement using [`versioned_entry!`](crate::versioned_entry), which also covers
+/// legacy records that were stored without a version tag.
+pub trait VersionedEntry: Sized {
+    /// The struct for the latest schema version
+    type Latest;
+
+    /// The latest schema version number
+    const LATEST_VERSION: u32;
+
+    /// Get the schema version of this entry
+    fn version(&self) -> u32;
+
+    /// Upgrade by exactly one version (the latest version is returned unchanged)
+    fn upgrade_step(self) -> Self;
+
+    /// Unwrap the latest version or return `self` unchanged
+    fn try_into_latest_version(self) -> Result<Self::Latest, Self>;
+
+    /// Wrap the latest version
+    fn from_latest(latest: Self::Latest) -> Self;
+
+    /// Check if this entry uses the latest schema version
+    fn is_latest(&self) -> bool {
+        self.version() == Self::LATEST_VERSION
+    }
+
+    /// Run the upgrade chain until the latest version is reached
+    fn into_latest(self) -> ExternResult<Self::Latest> {
+        let mut entry = self;
+
+        for _ in 0..=Self::LATEST_VERSION {
+            entry = match entry.try_into_latest_version() {
+                Ok(latest) => return Ok( latest ),
+                Err(entry) => entry.upgrade_step(),
+            };
+        }
+
+        Err(guest_error!(
+            "Upgrade chain did not reach the latest version ({})", Self::LATEST_VERSION
+        ))
+    }
+}
+
+/// Implement [`VersionedEntry`] for an enum of schema versions
+///
+/// Each older variant names the variant it upgrades to; the conversion between them is the
+/// `From` impl of the newer struct.  The version numbers should match the enum's serde tags.
+///
+/// Records written before the entry was versioned have no `version` tag.  Declare their struct as
+/// a final `#[serde(untagged)]` variant (requires `serde >= 1.0.181`) and give it version `0`; serde
+/// only tries it when no tag matches, and [`VersionedEntry::into_latest`] upgrades it like any
+/// other old version.
+///
+/// ##### Example: Basic Usage
+/// ```
+/// use hdi::prelude::*;
+/// use hdi_extensions::*;
+///
+/// # #[hdk_entry_helper]
+/// # #[derive(Clone)]
+/// # pub struct PostEntryV0 { pub message: String }
+/// # #[hdk_entry_helper]
+/// # #[derive(Clone)]
+/// # pub struct PostEntryV1 { pub message: String, pub tags: Vec<String> }
+/// # #[hdk_entry_helper]
+/// # #[derive(Clone)]
+/// # pub struct PostEntryV2 { pub text: String, pub tags: Vec<String> }
+/// # impl From<PostEntryV0> for PostEntryV1 {
+/// #     fn from(v0: PostEntryV0) -> Self { PostEntryV1 { message: v0.message, tags: vec![] } }
+/// # }
+/// # impl From<PostEntryV1> for PostEntryV2 {
+/// #     fn from(v1: PostEntryV1) -> Self { PostEntryV2 { text: v1.message, tags: v1.tags } }
+/// # }
+/// #[hdk_entry_helper]
+/// #[derive(Clone)]
+/// #[serde(tag = "version")]
+/// pub enum PostEntry {
+///     #[serde(rename = "1")]
+///     V1(PostEntryV1),
+///     #[serde(rename = "2")]
+///     V2(PostEntryV2),
+///     // Legacy records that were stored as a bare `PostEntryV0`
+///     #[serde(untagged)]
+///     V0(PostEntryV0),
+/// }
+///
+/// // Requires `From<PostEntryV0> for PostEntryV1` and `From<PostEntryV1> for PostEntryV2`
+/// versioned_entry!( PostEntry {
+///     0 => V0 -> V1,
+///     1 => V1 -> V2,
+///     2 => V2( PostEntryV2 ),
+/// });
+/// ```
+#[macro_export]
+macro_rules! versioned_entry {
+    ( $entry:ident { $( $body:tt )* } ) => {
+        $crate::versioned_entry!( @steps $entry [] $( $body )* );
+    };
+    ( @steps $entry:ident [ $( $steps:tt )* ] $version:literal => $from:ident -> $to:ident, $( $rest:tt )* ) => {
+        $crate::versioned_entry!( @steps $entry [ $( $steps )* ( $version, $from, $to ) ] $( $rest )* );
+    };
+    (
+        @steps $entry:ident [ $( ( $version:literal, $from:ident, $to:ident ) )* ]
+        $latest_version:literal => $latest:ident( $latest_ty:ty ) $(,)?
+    ) => {
+        impl $crate::VersionedEntry for $entry {
+            type Latest = $latest_ty;
+
+            const LATEST_VERSION: u32 = $latest_version;
+
+            fn version(&self) -> u32 {
+                match self {
+                    $( $entry::$from(_) => $version, )*
+                    $entry::$latest(_) => $latest_version,
+                }
+            }
+
+            #[allow(unreachable_patterns)]
+            fn upgrade_step(self) -> Self {
+                match self {
+                    $( $entry::$from(content) => $entry::$to( content.into() ), )*
+                    latest => latest,
+                }
+            }
+
+            #[allow(unreachable_patterns)]
+            fn try_into_latest_version(self) -> Result<Self::Latest, Self> {
+                match self {
+                    $entry::$latest(content) => Ok( content ),
+                    other => Err( other ),
+                }
+            }
+
+            fn from_latest(latest: Self::Latest) -> Self {
+                $entry::$latest( latest )
+            }
+        }
+    };
+}
+
+
+//
+// Summoning
+//
+/// Get a versioned app entry and upgrade it to the latest version
+///
+/// Untagged legacy records are upgraded too when `V` declares a legacy variant (see
+/// [`versioned_entry!`](crate::versioned_entry)).
+///
+/// ##### Example: Basic Usage
+/// ```ignore
+/// use hdi::prelude::*;
+/// use hdi_extensions::*;
+///
+/// fn test(addr: AnyLinkableHash) -> ExternResult<()> {
+///     let post : PostEntryV3 = summon_latest_app_entry::<PostEntry,_>( &addr )?;
+///     Ok(())
+/// }
+/// ```
+pub fn summon_latest_app_entry<V,E>(addr: &AnyLinkableHash) -> ExternResult<V::Latest>
+where
+    V: VersionedEntry + TryFrom<Record, Error = E> + TryFrom<Entry, Error = E>,
+    E: std::fmt::Debug,
+    WasmError: From<E>,
+{
+    summon_app_entry::<V,E>( addr )?.into_latest()
+}
+
+
+//
+// Validation
+//
+/// Require a new create or update to use the latest schema version
+///
+/// Historic records are still readable through the upgrade chain; only new writes are restricted.
+pub fn require_latest_version<V>(entry: &V) -> ValidateCallbackResult
+where
+    V: VersionedEntry,
+{
+    match entry.is_latest() {
+        true => ValidateCallbackResult::Valid,
+        false => ValidateCallbackResult::Invalid(
+            format!(
+                "New entries must use schema version {}; not version {}",
+                V::LATEST_VERSION, entry.version(),
+            )
+        ),
+    }
+}
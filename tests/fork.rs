@@ -0,0 +1,164 @@
+use hdi::prelude::*;
+use hdi_extensions::*;
+
+
+fn action_hash(n: u8) -> ActionHash {
+    ActionHash::from_raw_36( vec![ n; 36 ] )
+}
+
+fn agent(n: u8) -> AgentPubKey {
+    AgentPubKey::from_raw_36( vec![ n; 36 ] )
+}
+
+fn update(hash: u8, original: u8, author: u8, micros: i64) -> SignedActionHashed {
+    let action = Action::Update(Update {
+        author: agent( author ),
+        timestamp: Timestamp::from_micros( micros ),
+        action_seq: 1,
+        prev_action: action_hash( original ),
+        original_action_address: action_hash( original ),
+        original_entry_address: EntryHash::from_raw_36( vec![ 0; 36 ] ),
+        entry_type: EntryType::AgentPubKey,
+        entry_hash: EntryHash::from_raw_36( vec![ hash; 36 ] ),
+        weight: EntryRateWeight::default(),
+    });
+
+    SignedActionHashed::with_presigned(
+        ActionHashed::with_pre_hashed( action, action_hash( hash ) ),
+        Signature( [ 0; 64 ] ),
+    )
+}
+
+
+#[test]
+fn linear_chain_has_no_forks() {
+    let updates = vec![
+        update( 2, 1, 1, 10 ),
+        update( 3, 2, 1, 20 ),
+    ];
+
+    let resolution = resolve_head( &action_hash( 1 ), &updates, &LatestTimestamp ).unwrap();
+
+    assert_eq!( resolution.head, action_hash( 3 ) );
+    assert_eq!( resolution.path, vec![ action_hash( 1 ), action_hash( 2 ), action_hash( 3 ) ] );
+    assert!( !resolution.has_forks() );
+}
+
+#[test]
+fn candidate_order_does_not_change_head() {
+    let mut updates = vec![
+        update( 2, 1, 1, 10 ),
+        update( 3, 1, 1, 30 ),
+        update( 4, 3, 1, 40 ),
+    ];
+
+    let forward = resolve_head( &action_hash( 1 ), &updates, &LatestTimestamp ).unwrap();
+    updates.reverse();
+    let backward = resolve_head( &action_hash( 1 ), &updates, &LatestTimestamp ).unwrap();
+
+    assert_eq!( forward, backward );
+    assert_eq!( forward.head, action_hash( 4 ) );
+    assert_eq!( forward.forks, vec![ Fork {
+        original: action_hash( 1 ),
+        branches: vec![ action_hash( 3 ), action_hash( 2 ) ],
+    }]);
+}
+
+#[test]
+fn equal_timestamps_prefer_lowest_hash() {
+    let updates = vec![
+        update( 7, 1, 1, 10 ),
+        update( 5, 1, 1, 10 ),
+        update( 6, 1, 1, 10 ),
+    ];
+
+    let resolution = resolve_head( &action_hash( 1 ), &updates, &LatestTimestamp ).unwrap();
+
+    assert_eq!( resolution.head, action_hash( 5 ) );
+    assert_eq!(
+        resolution.forks[0].branches,
+        vec![ action_hash( 5 ), action_hash( 6 ), action_hash( 7 ) ]
+    );
+}
+
+#[test]
+fn lowest_hash_ignores_timestamps() {
+    let updates = vec![
+        update( 3, 1, 1, 99 ),
+        update( 2, 1, 1, 10 ),
+    ];
+
+    let resolution = resolve_head( &action_hash( 1 ), &updates, &LowestHash ).unwrap();
+
+    assert_eq!( resolution.head, action_hash( 2 ) );
+}
+
+#[test]
+fn author_priority_beats_newer_timestamps() {
+    let updates = vec![
+        update( 2, 1, 9, 50 ),
+        update( 3, 1, 8, 10 ),
+        update( 4, 1, 7, 90 ),
+    ];
+    let policy = AuthorPriority( vec![ agent( 8 ), agent( 9 ) ] );
+
+    let resolution = resolve_head( &action_hash( 1 ), &updates, &policy ).unwrap();
+
+    assert_eq!( resolution.head, action_hash( 3 ) );
+    assert_eq!(
+        resolution.forks[0].branches,
+        vec![ action_hash( 3 ), action_hash( 2 ), action_hash( 4 ) ]
+    );
+}
+
+#[test]
+fn closures_are_policies() {
+    let updates = vec![
+        update( 2, 1, 1, 10 ),
+        update( 3, 1, 1, 20 ),
+    ];
+    let oldest_first = |a: &SignedActionHashed, b: &SignedActionHashed| {
+        b.hashed.content.timestamp().cmp( &a.hashed.content.timestamp() )
+    };
+
+    let resolution = resolve_head( &action_hash( 1 ), &updates, &oldest_first ).unwrap();
+
+    assert_eq!( resolution.head, action_hash( 2 ) );
+}
+
+#[test]
+fn duplicate_candidates_are_ignored() {
+    let updates = vec![
+        update( 2, 1, 1, 10 ),
+        update( 2, 1, 1, 10 ),
+    ];
+
+    let resolution = resolve_head( &action_hash( 1 ), &updates, &LatestTimestamp ).unwrap();
+
+    assert_eq!( resolution.head, action_hash( 2 ) );
+    assert!( !resolution.has_forks() );
+}
+
+#[test]
+fn non_update_candidates_are_rejected() {
+    let mut candidate = update( 2, 1, 1, 10 );
+    candidate.hashed.content = Action::Delete(Delete {
+        author: agent( 1 ),
+        timestamp: Timestamp::from_micros( 10 ),
+        action_seq: 1,
+        prev_action: action_hash( 1 ),
+        deletes_address: action_hash( 1 ),
+        deletes_entry_address: EntryHash::from_raw_36( vec![ 0; 36 ] ),
+        weight: RateWeight::default(),
+    });
+
+    assert!( resolve_head( &action_hash( 1 ), &[ candidate ], &LatestTimestamp ).is_err() );
+}
+
+#[test]
+fn root_without_updates_is_the_head() {
+    let resolution = resolve_head( &action_hash( 1 ), &[], &LatestTimestamp ).unwrap();
+
+    assert_eq!( resolution.head, action_hash( 1 ) );
+    assert_eq!( resolution.path, vec![ action_hash( 1 ) ] );
+}
@@ -0,0 +1,169 @@
+use hdi::prelude::*;
+use hdi_extensions::*;
+use holo_hash::encode::holo_dht_location_bytes;
+
+
+fn raw_36(n: u8) -> Vec<u8> {
+    let core = vec![ n; 32 ];
+    [ core.clone(), holo_dht_location_bytes( &core ) ].concat()
+}
+
+fn error_message(error: WasmError) -> String {
+    match error.error {
+        WasmErrorInner::Guest(msg) => msg,
+        other => panic!("Expected a guest error; not {:?}", other ),
+    }
+}
+
+
+#[test]
+fn prefixes_round_trip() {
+    for kind in HashKind::ALL {
+        assert_eq!( HashKind::from_prefix( &kind.prefix() ), Some( kind ) );
+    }
+
+    assert_eq!( HashKind::from_prefix( &[ 0, 0, 0 ] ), None );
+}
+
+#[test]
+fn detect_kind_from_string() {
+    let action = ActionHash::from_raw_36( raw_36( 1 ) );
+    let entry = EntryHash::from_raw_36( raw_36( 2 ) );
+    let agent = AgentPubKey::from_raw_36( raw_36( 3 ) );
+    let dna = DnaHash::from_raw_36( raw_36( 4 ) );
+
+    assert_eq!( detect_hash_kind( &action.to_string() ).unwrap(), HashKind::Action );
+    assert_eq!( detect_hash_kind( &entry.to_string() ).unwrap(), HashKind::Entry );
+    assert_eq!( detect_hash_kind( &agent.to_string() ).unwrap(), HashKind::Agent );
+    assert_eq!( detect_hash_kind( &dna.to_string() ).unwrap(), HashKind::Dna );
+}
+
+#[test]
+fn decode_string_into_expected_type() {
+    let action = ActionHash::from_raw_36( raw_36( 1 ) );
+
+    let decoded : ActionHash = decode_hash_string( &action.to_string() ).unwrap();
+    assert_eq!( decoded, action );
+
+    let linkable : AnyLinkableHash = decode_hash_string( &action.to_string() ).unwrap();
+    assert_eq!( linkable, AnyLinkableHash::from( action.clone() ) );
+
+    let msg = error_message( decode_hash_string::<EntryHash>( &action.to_string() ).unwrap_err() );
+    assert!( msg.contains("has an Action hash prefix; expected an Entry hash"), "{}", msg );
+}
+
+#[test]
+fn agent_strings_become_entry_typed_linkable_hashes() {
+    let agent = AgentPubKey::from_raw_36( raw_36( 3 ) );
+
+    let linkable : AnyLinkableHash = decode_hash_string( &agent.to_string() ).unwrap();
+
+    assert_eq!( linkable.must_be_agent_pubkey().unwrap(), agent );
+}
+
+#[test]
+fn dna_hash_is_not_linkable() {
+    let dna = DnaHash::from_raw_36( raw_36( 4 ) );
+
+    let msg = error_message( decode_hash_string::<AnyLinkableHash>( &dna.to_string() ).unwrap_err() );
+
+    assert!( msg.contains("has a DNA hash prefix"), "{}", msg );
+}
+
+#[test]
+fn string_with_bad_location_bytes_is_reported() {
+    let mut raw = raw_36( 1 );
+    raw[35] ^= 0xff;
+    let input = ActionHash::from_raw_36( raw ).to_string();
+
+    let msg = error_message( DecodedHash::try_from_string( &input ).unwrap_err() );
+
+    assert!( msg.contains("DHT location bytes that do not match the Action hash core"), "{}", msg );
+}
+
+#[test]
+fn string_without_prefix_is_rejected() {
+    let input = ActionHash::from_raw_36( raw_36( 1 ) ).to_string();
+
+    let msg = error_message( DecodedHash::try_from_string( &input[1..] ).unwrap_err() );
+
+    assert!( msg.contains("missing 'u' prefix"), "{}", msg );
+}
+
+#[test]
+fn string_with_wrong_length_is_rejected() {
+    let input = ActionHash::from_raw_36( raw_36( 1 ) ).to_string();
+
+    let msg = error_message( DecodedHash::try_from_string( &input[..input.len() - 4] ).unwrap_err() );
+
+    assert!( msg.contains("a length of"), "{}", msg );
+}
+
+#[test]
+fn string_with_unknown_prefix_is_rejected() {
+    assert!( DecodedHash::try_from_string( &format!("u{}", "A".repeat(52) ) ).is_err() );
+}
+
+#[test]
+fn decode_all_byte_layouts() {
+    let action = ActionHash::from_raw_36( raw_36( 1 ) );
+    let raw_39 = action.get_raw_39().to_vec();
+
+    for bytes in [
+        raw_39.clone(),
+        raw_39[..35].to_vec(),
+        raw_39[3..].to_vec(),
+        raw_39[3..35].to_vec(),
+    ] {
+        assert_eq!( ActionHash::try_from_bytes( &bytes ).unwrap(), action );
+    }
+}
+
+#[test]
+fn missing_prefix_needs_a_single_expected_kind() {
+    let action = ActionHash::from_raw_36( raw_36( 1 ) );
+
+    let msg = error_message(
+        AnyLinkableHash::try_from_bytes( &action.get_raw_39()[3..] ).unwrap_err()
+    );
+
+    assert!( msg.contains("expected type is ambiguous"), "{}", msg );
+}
+
+#[test]
+fn bad_location_bytes_are_rejected() {
+    let mut raw_39 = ActionHash::from_raw_36( raw_36( 1 ) ).get_raw_39().to_vec();
+    raw_39[38] ^= 0xff;
+
+    assert!( ActionHash::try_from_bytes( &raw_39 ).is_err() );
+}
+
+#[test]
+fn decode_hex() {
+    let agent = AgentPubKey::from_raw_36( raw_36( 3 ) );
+    let hex : String = agent.get_raw_39().iter()
+        .map(|byte| format!("{:02x}", byte ) )
+        .collect();
+
+    assert_eq!( AgentPubKey::try_from_hex( &hex ).unwrap(), agent );
+    assert_eq!( AgentPubKey::try_from_hex( &format!("0x{}", hex ) ).unwrap(), agent );
+    assert_eq!( AgentPubKey::try_from_hex( &hex[6..] ).unwrap(), agent );
+
+    let msg = error_message( AgentPubKey::try_from_hex( &hex[1..] ).unwrap_err() );
+    assert!( msg.contains("odd number of digits"), "{}", msg );
+
+    assert!( AgentPubKey::try_from_hex( &"zz".repeat(39) ).is_err() );
+}
+
+#[test]
+fn decode_base64() {
+    let entry = EntryHash::from_raw_36( raw_36( 2 ) );
+    let encoded = entry.to_string();
+
+    assert_eq!( EntryHash::try_from_base64( &encoded ).unwrap(), entry );
+    assert_eq!( EntryHash::try_from_base64( &encoded[1..] ).unwrap(), entry );
+    assert!( EntryHash::try_from_base64( "not base64!" ).is_err() );
+
+    let msg = error_message( ActionHash::try_from_base64( &encoded ).unwrap_err() );
+    assert!( msg.contains("is valid base64 but"), "{}", msg );
+}
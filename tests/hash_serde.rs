@@ -0,0 +1,55 @@
+use hdi::prelude::*;
+use hdi_extensions::*;
+use holo_hash::encode::holo_dht_location_bytes;
+use serde::Deserialize;
+
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct GetPostInput {
+    #[serde(with = "hdi_extensions::hash_serde::action_hash")]
+    pub id: ActionHash,
+}
+
+fn action_hash(n: u8) -> ActionHash {
+    let core = vec![ n; 32 ];
+    ActionHash::from_raw_36( [ core.clone(), holo_dht_location_bytes( &core ) ].concat() )
+}
+
+
+#[test]
+fn deserialize_from_json_string() {
+    let id = action_hash( 1 );
+    let json = format!(r#"{{ "id": "{}" }}"#, id );
+
+    let input : GetPostInput = serde_json::from_str( &json ).unwrap();
+
+    assert_eq!( input.id, id );
+}
+
+#[test]
+fn deserialize_from_msgpack_bin() {
+    let input = GetPostInput { id: action_hash( 1 ) };
+    let bytes = rmp_serde::to_vec_named( &input ).unwrap();
+
+    assert_eq!( rmp_serde::from_slice::<GetPostInput>( &bytes ).unwrap(), input );
+}
+
+#[test]
+fn deserialize_from_byte_sequence() {
+    let id = action_hash( 1 );
+    let json = serde_json::json!({ "id": id.get_raw_39() });
+
+    let input : GetPostInput = serde_json::from_value( json ).unwrap();
+
+    assert_eq!( input.id, id );
+}
+
+#[test]
+fn wrong_kind_string_is_rejected() {
+    let entry = EntryHash::from_raw_36( action_hash( 1 ).get_raw_36().to_vec() );
+    let json = format!(r#"{{ "id": "{}" }}"#, entry );
+
+    let error = serde_json::from_str::<GetPostInput>( &json ).unwrap_err().to_string();
+
+    assert!( error.contains("has an Entry hash prefix; expected an Action hash"), "{}", error );
+}
@@ -0,0 +1,111 @@
+use hdi::prelude::*;
+use hdi_extensions::*;
+use holo_hash::encode::holo_dht_location_bytes;
+
+
+fn raw_36(n: u8) -> Vec<u8> {
+    let core = vec![ n; 32 ];
+    [ core.clone(), holo_dht_location_bytes( &core ) ].concat()
+}
+
+fn base() -> AnyLinkableHash {
+    ActionHash::from_raw_36( raw_36( 1 ) ).into()
+}
+
+fn query(after: Option<i64>, before: Option<i64>) -> LinkQueryInput<u8> {
+    let mut input = LinkQueryInput::new( base() );
+    input.after = after.map( Timestamp::from_micros );
+    input.before = before.map( Timestamp::from_micros );
+    input
+}
+
+fn error_message(error: WasmError) -> String {
+    match error.error {
+        WasmErrorInner::Guest(msg) => msg,
+        other => panic!("Expected a guest error; not {:?}", other ),
+    }
+}
+
+
+#[test]
+fn author_accepts_hash_string() {
+    let author = AgentPubKey::from_raw_36( raw_36( 2 ) );
+    let json = format!(r#"{{ "base": "{}", "author": "{}" }}"#, base(), author );
+
+    let input : LinkQueryInput<u8> = serde_json::from_str( &json ).unwrap();
+
+    assert_eq!( input.base, base() );
+    assert_eq!( input.author, Some( author ) );
+}
+
+#[test]
+fn author_may_be_missing_or_null() {
+    let missing = format!(r#"{{ "base": "{}" }}"#, base() );
+    let null = format!(r#"{{ "base": "{}", "author": null }}"#, base() );
+
+    assert_eq!( serde_json::from_str::<LinkQueryInput<u8>>( &missing ).unwrap().author, None );
+    assert_eq!( serde_json::from_str::<LinkQueryInput<u8>>( &null ).unwrap().author, None );
+}
+
+#[test]
+fn author_round_trips_as_msgpack() {
+    let mut input = LinkQueryInput::<u8>::new( base() );
+    input.author = Some( AgentPubKey::from_raw_36( raw_36( 2 ) ) );
+
+    let bytes = rmp_serde::to_vec_named( &input ).unwrap();
+    let decoded : LinkQueryInput<u8> = rmp_serde::from_slice( &bytes ).unwrap();
+
+    assert_eq!( decoded.author, input.author );
+}
+
+#[test]
+fn author_rejects_wrong_kind() {
+    let entry = EntryHash::from_raw_36( raw_36( 2 ) );
+    let json = format!(r#"{{ "base": "{}", "author": "{}" }}"#, base(), entry );
+
+    let error = serde_json::from_str::<LinkQueryInput<u8>>( &json ).unwrap_err().to_string();
+
+    assert!( error.contains("expected an Agent hash"), "{}", error );
+}
+
+#[test]
+fn timestamp_range_is_inclusive_after_and_exclusive_before() {
+    let input = query( Some( 10 ), Some( 20 ) );
+
+    assert!( !input.matches_timestamp( &Timestamp::from_micros( 9 ) ) );
+    assert!( input.matches_timestamp( &Timestamp::from_micros( 10 ) ) );
+    assert!( input.matches_timestamp( &Timestamp::from_micros( 19 ) ) );
+    assert!( !input.matches_timestamp( &Timestamp::from_micros( 20 ) ) );
+}
+
+#[test]
+fn open_timestamp_bounds_match_everything() {
+    assert!( query( None, None ).matches_timestamp( &Timestamp::from_micros( 0 ) ) );
+    assert!( query( Some( 10 ), None ).matches_timestamp( &Timestamp::from_micros( i64::MAX ) ) );
+    assert!( query( None, Some( 10 ) ).matches_timestamp( &Timestamp::from_micros( i64::MIN ) ) );
+}
+
+#[test]
+fn empty_time_range_is_rejected() {
+    assert!( query( Some( 10 ), Some( 20 ) ).check().is_ok() );
+    assert!( query( Some( 10 ), None ).check().is_ok() );
+
+    let msg = error_message( query( Some( 20 ), Some( 20 ) ).check().unwrap_err() );
+    assert_eq!( msg, "Link query time range is empty: 20 >= 20" );
+
+    assert!( query( Some( 30 ), Some( 20 ) ).check().is_err() );
+}
+
+#[test]
+fn linkable_kind_without_summoning() {
+    let entry : AnyLinkableHash = EntryHash::from_raw_36( raw_36( 2 ) ).into();
+
+    assert_eq!( require_linkable_kind( "base", &base(), &[ HashKind::Action ] ).unwrap(), HashKind::Action );
+    assert_eq!(
+        require_linkable_kind( "base", &entry, &[ HashKind::Entry, HashKind::Agent ] ).unwrap(),
+        HashKind::Entry
+    );
+
+    let msg = error_message( require_linkable_kind( "base", &base(), &[ HashKind::Agent ] ).unwrap_err() );
+    assert!( msg.starts_with("Link base must be "), "{}", msg );
+}
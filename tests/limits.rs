@@ -0,0 +1,174 @@
+use std::collections::BTreeMap;
+use hdi::prelude::*;
+use hdi_extensions::*;
+
+
+#[derive(EntryLimits, Serialize)]
+struct PostEntry {
+    #[limit(min_len = 1, max_len = 5)]
+    #[limit(max_bytes = 8)]
+    pub message: String,
+    #[limit(max_len = 2)]
+    pub tags: Vec<String>,
+    #[limit(max_bytes = 4)]
+    pub thumbnail: Option<Vec<u8>>,
+    pub unchecked: String,
+}
+
+fn post(message: &str, tags: usize) -> PostEntry {
+    PostEntry {
+        message: message.to_string(),
+        tags: vec![ "tag".to_string(); tags ],
+        thumbnail: None,
+        unchecked: "x".repeat( 1_000 ),
+    }
+}
+
+fn app_entry<T>(value: &T) -> Entry
+where
+    T: Serialize,
+{
+    Entry::App(AppEntryBytes(SerializedBytes::from(UnsafeBytes::from(
+        rmp_serde::to_vec_named( value ).unwrap()
+    ))))
+}
+
+
+#[test]
+fn field_limits_pass() {
+    assert_eq!( post( "hello", 2 ).limit_violation(), None );
+    assert_eq!( post( "hello", 2 ).check_limits(), ValidateCallbackResult::Valid );
+}
+
+#[test]
+fn field_limits_name_the_field_and_limit() {
+    assert_eq!(
+        post( "", 0 ).limit_violation(),
+        Some("Field 'message' violates min_len: 0 < 1".to_string())
+    );
+    assert_eq!(
+        post( "hello!", 0 ).limit_violation(),
+        Some("Field 'message' violates max_len: 6 > 5".to_string())
+    );
+    assert_eq!(
+        post( "hi", 3 ).limit_violation(),
+        Some("Field 'tags' violates max_len: 3 > 2".to_string())
+    );
+}
+
+#[test]
+fn max_len_counts_characters_and_max_bytes_counts_bytes() {
+    // 3 characters, 9 bytes
+    assert_eq!(
+        post( "日本語", 0 ).limit_violation(),
+        Some("Field 'message' violates max_bytes: 9 > 8".to_string())
+    );
+
+    let mut entry = post( "hi", 0 );
+    entry.thumbnail = Some( vec![ 0; 5 ] );
+
+    assert_eq!(
+        entry.check_limits(),
+        ValidateCallbackResult::Invalid("Field 'thumbnail' violates max_bytes: 5 > 4".to_string())
+    );
+}
+
+#[test]
+fn entry_within_limits_is_valid() {
+    let entry = app_entry( &BTreeMap::from([ ( "message", vec![ "hello" ] ) ]) );
+
+    assert_eq!(
+        check_entry_limits( &entry, &Limits {
+            max_bytes: Some( 100 ),
+            max_string_len: Some( 5 ),
+            max_collection_len: Some( 1 ),
+        }).unwrap(),
+        ValidateCallbackResult::Valid
+    );
+}
+
+#[test]
+fn field_names_are_not_string_limited() {
+    #[derive(Serialize)]
+    struct Post {
+        pub description: String,
+    }
+
+    let entry = app_entry( &Post { description: "hi".to_string() } );
+
+    assert_eq!(
+        check_entry_limits( &entry, &Limits {
+            max_string_len: Some( 5 ),
+            ..Default::default()
+        }).unwrap(),
+        ValidateCallbackResult::Valid
+    );
+}
+
+#[test]
+fn entry_too_many_bytes() {
+    let entry = app_entry( &"x".repeat( 100 ) );
+
+    let result = check_entry_limits( &entry, &Limits {
+        max_bytes: Some( 50 ),
+        ..Default::default()
+    }).unwrap();
+
+    assert_eq!( result, ValidateCallbackResult::Invalid("Entry is 102 bytes; the limit is 50".to_string()) );
+}
+
+#[test]
+fn entry_nested_string_reports_path() {
+    let entry = app_entry( &BTreeMap::from([ ( "tags", vec![ "ok", "too long" ] ) ]) );
+
+    let result = check_entry_limits( &entry, &Limits {
+        max_string_len: Some( 4 ),
+        ..Default::default()
+    }).unwrap();
+
+    assert_eq!(
+        result,
+        ValidateCallbackResult::Invalid("String at '$.tags[1]' has 8 characters; the limit is 4".to_string())
+    );
+}
+
+#[test]
+fn entry_collection_too_long() {
+    let entry = app_entry( &BTreeMap::from([ ( "tags", vec![ 1, 2, 3 ] ) ]) );
+
+    let result = check_entry_limits( &entry, &Limits {
+        max_collection_len: Some( 2 ),
+        ..Default::default()
+    }).unwrap();
+
+    assert_eq!(
+        result,
+        ValidateCallbackResult::Invalid("Array at '$.tags' has 3 items; the limit is 2".to_string())
+    );
+}
+
+#[test]
+fn entry_limits_cover_fields_without_attributes() {
+    let entry = app_entry( &post( "hi", 0 ) );
+
+    assert_eq!( post( "hi", 0 ).check_limits(), ValidateCallbackResult::Valid );
+    assert_eq!(
+        check_entry_limits( &entry, &Limits {
+            max_string_len: Some( 280 ),
+            ..Default::default()
+        }).unwrap(),
+        ValidateCallbackResult::Invalid("String at '$.unchecked' has 1000 characters; the limit is 280".to_string())
+    );
+}
+
+#[test]
+fn non_app_entries_are_valid() {
+    let entry = Entry::Agent( AgentPubKey::from_raw_36( vec![ 0; 36 ] ) );
+
+    let result = check_entry_limits( &entry, &Limits {
+        max_bytes: Some( 0 ),
+        ..Default::default()
+    }).unwrap();
+
+    assert_eq!( result, ValidateCallbackResult::Valid );
+}
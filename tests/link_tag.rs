@@ -0,0 +1,92 @@
+use hdi::prelude::*;
+use hdi_extensions::*;
+
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct PostTag {
+    pub published_at: u64,
+    pub title: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct CommentTag {
+    pub depth: u8,
+}
+
+fn post_tag(title_len: usize) -> TypedLinkTag<PostTag> {
+    TypedLinkTag::new( PostTag {
+        published_at: 1_700_000_000,
+        title: "x".repeat( title_len ),
+    })
+}
+
+fn error_message(error: WasmError) -> String {
+    match error.error {
+        WasmErrorInner::Guest(msg) => msg,
+        other => panic!("Expected a guest error; not {:?}", other ),
+    }
+}
+
+
+#[test]
+fn encode_decode_round_trip() {
+    let typed = post_tag( 10 );
+
+    let tag = typed.encode().unwrap();
+    let decoded : TypedLinkTag<PostTag> = TypedLinkTag::decode( &tag ).unwrap();
+
+    assert_eq!( decoded, typed );
+    assert_eq!( LinkTag::try_from( typed.clone() ).unwrap(), tag );
+    assert_eq!( TypedLinkTag::<PostTag>::try_from( &tag ).unwrap(), typed );
+}
+
+#[test]
+fn encode_rejects_oversized_tag() {
+    let msg = error_message( post_tag( MAX_LINK_TAG_SIZE ).encode().unwrap_err() );
+
+    assert!( msg.contains(&format!("; the limit is {}", MAX_LINK_TAG_SIZE )), "{}", msg );
+    assert!( msg.starts_with("Encoded link tag is "), "{}", msg );
+}
+
+#[test]
+fn decode_rejects_schema_mismatch() {
+    let tag = post_tag( 10 ).encode().unwrap();
+
+    let msg = error_message( TypedLinkTag::<CommentTag>::decode( &tag ).unwrap_err() );
+
+    assert!( msg.starts_with("Link tag does not match the expected schema"), "{}", msg );
+}
+
+#[test]
+fn validate_matching_tag() {
+    let tag = post_tag( 10 ).encode().unwrap();
+
+    assert_eq!( validate_link_tag::<PostTag>( &tag ).unwrap(), ValidateCallbackResult::Valid );
+}
+
+#[test]
+fn validate_schema_mismatch_is_invalid() {
+    let tag = post_tag( 10 ).encode().unwrap();
+
+    match validate_link_tag::<CommentTag>( &tag ).unwrap() {
+        ValidateCallbackResult::Invalid(msg) => assert!(
+            msg.starts_with("Link tag does not match the expected schema"), "{}", msg
+        ),
+        other => panic!("Expected invalid; not {:?}", other ),
+    }
+
+    assert!( matches!(
+        validate_link_tag::<PostTag>( &LinkTag::new( vec![ 0xc1 ] ) ).unwrap(),
+        ValidateCallbackResult::Invalid(_)
+    ));
+}
+
+#[test]
+fn validate_oversized_tag_is_invalid() {
+    let tag = LinkTag::new( vec![ 0; MAX_LINK_TAG_SIZE + 1 ] );
+
+    assert_eq!(
+        validate_link_tag::<PostTag>( &tag ).unwrap(),
+        ValidateCallbackResult::Invalid("Link tag is 1001 bytes; the limit is 1000".to_string())
+    );
+}
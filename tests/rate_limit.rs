@@ -0,0 +1,41 @@
+use core::time::Duration;
+use hdi::prelude::*;
+use hdi_extensions::*;
+
+
+#[derive(Debug, PartialEq)]
+enum EntryTypesUnit {
+    Post,
+}
+
+impl TryFrom<ScopedEntryDefIndex> for EntryTypesUnit {
+    type Error = WasmError;
+
+    fn try_from(_: ScopedEntryDefIndex) -> Result<Self, Self::Error> {
+        Ok( EntryTypesUnit::Post )
+    }
+}
+
+
+#[test]
+fn updates_are_not_rate_limited() {
+    let post_type = EntryType::App(AppEntryDef::new( 0.into(), 0.into(), EntryVisibility::Public ));
+    let update = Update {
+        author: AgentPubKey::from_raw_36( vec![ 1; 36 ] ),
+        timestamp: Timestamp::from_micros( 10 ),
+        action_seq: 30,
+        prev_action: ActionHash::from_raw_36( vec![ 2; 36 ] ),
+        original_action_address: ActionHash::from_raw_36( vec![ 3; 36 ] ),
+        original_entry_address: EntryHash::from_raw_36( vec![ 4; 36 ] ),
+        entry_type: post_type,
+        entry_hash: EntryHash::from_raw_36( vec![ 5; 36 ] ),
+        weight: EntryRateWeight::default(),
+    };
+
+    // A limit of 0 would reject any counted action, and no chain is available to walk
+    let result = RateLimit::new( EntryTypesUnit::Post, 0, Duration::from_secs( 3600 ) )
+        .check( &update )
+        .unwrap();
+
+    assert_eq!( result, ValidateCallbackResult::Valid );
+}
@@ -0,0 +1,61 @@
+use hdi::prelude::*;
+use hdi_extensions::*;
+
+
+fn hashes() -> Vec<AnyDhtHash> {
+    vec![ ActionHash::from_raw_36( vec![ 1; 36 ] ).into() ]
+}
+
+
+#[test]
+fn ok_is_found() {
+    let summoned = Summoned::from_result( Ok( 42 ), hashes() ).unwrap();
+
+    assert_eq!( summoned, Summoned::Found( 42 ) );
+    assert_eq!( summoned.into_validate_callback_result(), ValidateCallbackResult::Valid );
+}
+
+#[test]
+fn host_short_circuit_is_unresolved() {
+    let result : ExternResult<u8> = Err(wasm_error!(WasmErrorInner::HostShortCircuit( vec![] )));
+
+    let summoned = Summoned::from_result( result, hashes() ).unwrap();
+
+    assert_eq!( summoned, Summoned::Unresolved( UnresolvedDependencies::Hashes( hashes() ) ) );
+    assert_eq!(
+        summoned.into_validate_callback_result(),
+        ValidateCallbackResult::UnresolvedDependencies( UnresolvedDependencies::Hashes( hashes() ) )
+    );
+}
+
+#[test]
+fn guest_error_is_invalid() {
+    let result : ExternResult<u8> = Err(guest_error!("Wrong entry type"));
+
+    let summoned = Summoned::from_result( result, hashes() ).unwrap();
+
+    assert_eq!( summoned, Summoned::Invalid( "Wrong entry type".to_string() ) );
+    assert_eq!(
+        summoned.into_validate_callback_result(),
+        ValidateCallbackResult::Invalid( "Wrong entry type".to_string() )
+    );
+}
+
+#[test]
+fn other_errors_are_returned() {
+    let result : ExternResult<u8> = Err(wasm_error!(WasmErrorInner::Host( "Host failed".to_string() )));
+
+    let error = Summoned::from_result( result, hashes() ).unwrap_err();
+
+    assert_eq!( error.error, WasmErrorInner::Host( "Host failed".to_string() ) );
+}
+
+#[test]
+fn map_keeps_unfound_states() {
+    assert_eq!( Summoned::Found( 2 ).map(|n| n * 2 ), Summoned::Found( 4 ) );
+    assert_eq!(
+        Summoned::<u8>::Invalid( "bad".to_string() ).map(|n| n * 2 ),
+        Summoned::Invalid( "bad".to_string() )
+    );
+    assert!( !Summoned::<u8>::Unresolved( UnresolvedDependencies::Hashes( hashes() ) ).is_found() );
+}
@@ -0,0 +1,111 @@
+use hdi::prelude::*;
+use hdi_extensions::*;
+
+
+fn unresolved(n: u8) -> Validation {
+    Validation::unresolved( UnresolvedDependencies::Hashes(vec![
+        ActionHash::from_raw_36( vec![ n; 36 ] ).into(),
+    ]))
+}
+
+fn unresolved_hashes(validation: Validation) -> Vec<AnyDhtHash> {
+    match validation.into_inner() {
+        ValidateCallbackResult::UnresolvedDependencies(UnresolvedDependencies::Hashes(hashes)) => hashes,
+        other => panic!("Expected unresolved hashes; not {:?}", other ),
+    }
+}
+
+
+#[test]
+fn merge_valid_is_identity() {
+    assert_eq!( Validation::valid().merge( Validation::valid() ), Validation::valid() );
+    assert_eq!( Validation::valid().merge( unresolved( 1 ) ), unresolved( 1 ) );
+    assert_eq!( unresolved( 1 ).merge( Validation::valid() ), unresolved( 1 ) );
+}
+
+#[test]
+fn merge_invalid_wins() {
+    assert_eq!(
+        unresolved( 1 ).merge( Validation::invalid("bad") ),
+        Validation::invalid("bad")
+    );
+    assert_eq!(
+        Validation::invalid("first").merge( Validation::invalid("second") ),
+        Validation::invalid("first")
+    );
+}
+
+#[test]
+fn merge_unresolved_combines_hashes() {
+    let merged = unresolved( 1 ).merge( unresolved( 2 ) ).merge( unresolved( 1 ) );
+
+    assert_eq!( unresolved_hashes( merged ).len(), 2 );
+}
+
+#[test]
+fn all_stops_at_first_invalid() {
+    let mut evaluated = 0;
+    let checks = [ true, false, true ].into_iter()
+        .map(|ok| {
+            evaluated += 1;
+            Validation::check( ok, format!("check {}", evaluated ) )
+        });
+
+    assert_eq!( Validation::all( checks ), Validation::invalid("check 2") );
+    assert_eq!( evaluated, 2 );
+}
+
+#[test]
+fn all_merges_unresolved() {
+    let result = Validation::all([ Validation::valid(), unresolved( 1 ), unresolved( 2 ) ]);
+
+    assert_eq!( unresolved_hashes( result ).len(), 2 );
+    assert_eq!( Validation::all([]), Validation::valid() );
+}
+
+#[test]
+fn any_stops_at_first_valid() {
+    let result = Validation::any([ Validation::invalid("a"), Validation::valid(), unresolved( 1 ) ]);
+
+    assert!( result.is_valid() );
+}
+
+#[test]
+fn any_prefers_unresolved_over_invalid() {
+    let result = Validation::any([ Validation::invalid("a"), unresolved( 1 ) ]);
+
+    assert!( result.is_unresolved() );
+}
+
+#[test]
+fn any_joins_invalid_messages() {
+    assert_eq!(
+        Validation::any([ Validation::invalid("a"), Validation::invalid("b") ]),
+        Validation::invalid("a; b")
+    );
+    assert!( Validation::any([]).is_invalid() );
+}
+
+#[test]
+fn and_then_skips_after_invalid() {
+    let result = Validation::invalid("first")
+        .and_then(|| panic!("Should not run") )
+        .unwrap();
+
+    assert_eq!( result, Validation::invalid("first") );
+
+    let result = Validation::valid()
+        .and_then(|| Ok( Validation::invalid("second") ) )
+        .unwrap();
+
+    assert_eq!( result, Validation::invalid("second") );
+}
+
+#[test]
+fn with_context_only_changes_invalid() {
+    assert_eq!(
+        Validation::invalid("too short").with_context("message"),
+        Validation::invalid("message: too short")
+    );
+    assert_eq!( Validation::valid().with_context("message"), Validation::valid() );
+}
@@ -0,0 +1,117 @@
+use hdi::prelude::*;
+use hdi_extensions::*;
+
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PostEntryV0 {
+    pub message: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PostEntryV1 {
+    pub message: String,
+    pub tags: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PostEntryV2 {
+    pub text: String,
+    pub tags: Vec<String>,
+}
+
+impl From<PostEntryV0> for PostEntryV1 {
+    fn from(v0: PostEntryV0) -> Self {
+        PostEntryV1 {
+            message: v0.message,
+            tags: vec![],
+        }
+    }
+}
+
+impl From<PostEntryV1> for PostEntryV2 {
+    fn from(v1: PostEntryV1) -> Self {
+        PostEntryV2 {
+            text: v1.message,
+            tags: v1.tags,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "version")]
+pub enum PostEntry {
+    #[serde(rename = "1")]
+    V1(PostEntryV1),
+    #[serde(rename = "2")]
+    V2(PostEntryV2),
+    #[serde(untagged)]
+    V0(PostEntryV0),
+}
+
+versioned_entry!( PostEntry {
+    0 => V0 -> V1,
+    1 => V1 -> V2,
+    2 => V2( PostEntryV2 ),
+});
+
+
+fn decode<T>(value: &T) -> PostEntry
+where
+    T: Serialize,
+{
+    rmp_serde::from_slice( &rmp_serde::to_vec_named( value ).unwrap() ).unwrap()
+}
+
+
+#[test]
+fn legacy_untagged_record_is_upgraded() {
+    let entry = decode( &PostEntryV0 {
+        message: "hello".to_string(),
+    });
+
+    assert_eq!( entry.version(), 0 );
+    assert!( !entry.is_latest() );
+    assert_eq!( entry.into_latest().unwrap(), PostEntryV2 {
+        text: "hello".to_string(),
+        tags: vec![],
+    });
+}
+
+#[test]
+fn tagged_records_are_not_mistaken_for_legacy() {
+    let v1 = PostEntry::V1(PostEntryV1 {
+        message: "hello".to_string(),
+        tags: vec![ "a".to_string() ],
+    });
+
+    assert_eq!( decode( &v1 ), v1 );
+    assert_eq!( decode( &v1 ).into_latest().unwrap(), PostEntryV2 {
+        text: "hello".to_string(),
+        tags: vec![ "a".to_string() ],
+    });
+}
+
+#[test]
+fn latest_version_is_unchanged() {
+    let latest = PostEntryV2 {
+        text: "hello".to_string(),
+        tags: vec![],
+    };
+    let entry = decode( &PostEntry::from_latest( latest.clone() ) );
+
+    assert!( entry.is_latest() );
+    assert_eq!( require_latest_version( &entry ), ValidateCallbackResult::Valid );
+    assert_eq!( entry.into_latest().unwrap(), latest );
+}
+
+#[test]
+fn new_writes_must_use_latest_version() {
+    let legacy = PostEntry::V0(PostEntryV0 {
+        message: "hello".to_string(),
+    });
+
+    assert_eq!(
+        require_latest_version( &legacy ),
+        ValidateCallbackResult::Invalid("New entries must use schema version 2; not version 0".to_string())
+    );
+}